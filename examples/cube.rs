@@ -0,0 +1,70 @@
+use bevy::prelude::*;
+use bevy_orbit_camera::{OrbitCamera, OrbitCameraPlugin, OrbitCameraTarget};
+use std::f32::consts::FRAC_PI_4;
+
+fn main() {
+    App::build()
+        .add_resource(Msaa { samples: 4 })
+        .add_default_plugins()
+        .add_plugin(OrbitCameraPlugin)
+        .add_startup_system(setup.system())
+        .add_system(move_cube.system())
+        .run();
+}
+
+struct Cube;
+
+/// set up a simple 3D scene
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    // add entities to the world
+
+    // store the cube entity
+    let cube_entity = commands
+        // spawn a cube
+        .spawn(PbrComponents {
+            mesh: meshes.add(Mesh::from(shape::Cube { size: 1.0 })),
+            material: materials.add(Color::rgb(0.8, 0.7, 0.6).into()),
+            transform: Transform::from_translation(Vec3::new(0.0, 1.0, 0.0)),
+            ..Default::default()
+        })
+        .with(Cube)
+        .with(OrbitCameraTarget::default())
+        .current_entity();
+    commands
+        // plane
+        .spawn(PbrComponents {
+            mesh: meshes.add(Mesh::from(shape::Plane { size: 10.0 })),
+            material: materials.add(Color::rgb(0.3, 0.5, 0.3).into()),
+            ..Default::default()
+        })
+        // light
+        .spawn(LightComponents {
+            transform: Transform::from_translation(Vec3::new(4.0, 8.0, 4.0)),
+            ..Default::default()
+        })
+        // camera
+        .spawn(Camera3dComponents {
+            transform: Transform::from_translation(Vec3::new(-3.0, 5.0, 8.0))
+                .looking_at(Vec3::default(), Vec3::unit_y()),
+            ..Default::default()
+        })
+        .with(OrbitCamera::new(
+            cube_entity, // provide the cube entity to the OrbitCamera
+            50.0,        // 50.0 units from the origin of the entity
+            FRAC_PI_4,   // 45 degrees from horizontal to vertical
+            FRAC_PI_4,   // 45 degrees counter-clockwise from Z axis
+        ));
+}
+
+fn move_cube(time: Res<Time>, mut cube_query: Query<(&Cube, &mut Transform)>) {
+    let dt = time.delta_seconds;
+    let origin = Vec3::new(0.0, 11.0, -10.0);
+    if let Some((_, mut cube_transform)) = cube_query.iter().iter().next() {
+        let velocity = (cube_transform.translation - origin).cross(Vec3::unit_y());
+        cube_transform.translation += velocity * dt;
+    }
+}
@@ -0,0 +1,177 @@
+use crate::{OrbitCamera, OrbitView};
+use bevy::prelude::*;
+use std::f32::consts::PI;
+
+/// How an animation progresses over its duration
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Easing {
+    Linear,
+    EaseIn,
+    EaseOut,
+    EaseInOut,
+    /// Takes the fraction of the duration that has passed, from 0 to 1, and returns the
+    /// fraction of the way to the destination
+    Custom(fn(f32) -> f32),
+}
+
+impl Default for Easing {
+    fn default() -> Self {
+        Easing::EaseInOut
+    }
+}
+
+impl Easing {
+    pub fn apply(&self, t: f32) -> f32 {
+        match *self {
+            Easing::Linear => t,
+            Easing::EaseIn => t * t * t,
+            Easing::EaseOut => 1.0 - (1.0 - t).powi(3),
+            Easing::EaseInOut => {
+                if t < 0.5 {
+                    4.0 * t * t * t
+                } else {
+                    1.0 - (-2.0 * t + 2.0).powi(3) / 2.0
+                }
+            }
+            Easing::Custom(easing) => easing(t),
+        }
+    }
+}
+
+/// An `OrbitCamera` moving from one view to another, started by `OrbitCamera::animate_to`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OrbitAnimation {
+    pub(crate) from: OrbitView,
+    pub(crate) to: OrbitView,
+    pub(crate) duration: f32,
+    pub(crate) elapsed: f32,
+    pub(crate) easing: Easing,
+}
+
+impl OrbitAnimation {
+    pub(crate) fn new(from: OrbitView, to: OrbitView, duration: f32, easing: Easing) -> Self {
+        Self {
+            from,
+            to,
+            duration,
+            elapsed: 0.0,
+            easing,
+        }
+    }
+    pub fn from(&self) -> OrbitView {
+        self.from
+    }
+    pub fn to(&self) -> OrbitView {
+        self.to
+    }
+    /// The fraction of the duration that has passed, from 0 to 1
+    pub fn progress(&self) -> f32 {
+        if self.duration > 0.0 {
+            (self.elapsed / self.duration).min(1.0)
+        } else {
+            1.0
+        }
+    }
+    /// The view at the current progress, where the yaw turns the shortest way around
+    /// unless `unbounded_yaw` is false
+    fn view(&self, unbounded_yaw: bool) -> OrbitView {
+        let t = self.easing.apply(self.progress());
+        let lerp = |from: f32, to: f32| from + (to - from) * t;
+        let yaw_change = if unbounded_yaw {
+            OrbitCamera::wrap(self.to.yaw - self.from.yaw, -PI, PI)
+        } else {
+            self.to.yaw - self.from.yaw
+        };
+        OrbitView {
+            focus: self.from.focus.lerp(self.to.focus, t),
+            distance: lerp(self.from.distance, self.to.distance),
+            pitch: lerp(self.from.pitch, self.to.pitch),
+            yaw: self.from.yaw + yaw_change * t,
+        }
+    }
+}
+
+/// Sent when an `OrbitCamera` reaches the end of an animation
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OrbitCameraAnimationFinished {
+    pub camera: Entity,
+}
+
+// core
+pub fn animate_camera(
+    time: Res<Time>,
+    mut animation_finished_events: ResMut<Events<OrbitCameraAnimationFinished>>,
+    mut camera_query: Query<(Entity, &mut OrbitCamera)>,
+) {
+    for (entity, mut orbit_camera) in &mut camera_query.iter() {
+        let mut animation = match orbit_camera.animation {
+            Some(animation) => animation,
+            None => continue,
+        };
+        animation.elapsed += time.delta_seconds;
+        let unbounded_yaw = orbit_camera.yaw_limits().is_none();
+        orbit_camera.set_view(animation.view(unbounded_yaw));
+        if animation.progress() >= 1.0 {
+            orbit_camera.animation = None;
+            animation_finished_events.send(OrbitCameraAnimationFinished { camera: entity });
+        } else {
+            orbit_camera.animation = Some(animation);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::assert_close;
+
+    const EASINGS: [Easing; 4] = [
+        Easing::Linear,
+        Easing::EaseIn,
+        Easing::EaseOut,
+        Easing::EaseInOut,
+    ];
+
+    #[test]
+    fn easings_start_at_0_and_end_at_1() {
+        for easing in EASINGS.iter() {
+            assert_close(easing.apply(0.0), 0.0);
+            assert_close(easing.apply(1.0), 1.0);
+        }
+    }
+
+    #[test]
+    fn easings_never_go_backwards() {
+        for easing in EASINGS.iter() {
+            let mut previous = easing.apply(0.0);
+            for step in 1..=100 {
+                let eased = easing.apply(step as f32 / 100.0);
+                assert!(eased >= previous, "{:?} went backwards", easing);
+                previous = eased;
+            }
+        }
+    }
+
+    #[test]
+    fn ease_in_out_is_symmetric() {
+        assert_close(Easing::EaseInOut.apply(0.5), 0.5);
+        let early = Easing::EaseInOut.apply(0.25);
+        let late = Easing::EaseInOut.apply(0.75);
+        assert_close(early + late, 1.0);
+        assert!(Easing::EaseIn.apply(0.5) < 0.5);
+        assert!(Easing::EaseOut.apply(0.5) > 0.5);
+    }
+
+    #[test]
+    fn custom_easing_is_called() {
+        fn step(t: f32) -> f32 {
+            if t < 0.5 {
+                0.0
+            } else {
+                1.0
+            }
+        }
+        assert_eq!(Easing::Custom(step).apply(0.4), 0.0);
+        assert_eq!(Easing::Custom(step).apply(0.6), 1.0);
+    }
+}
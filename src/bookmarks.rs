@@ -0,0 +1,250 @@
+use crate::{Easing, OrbitCamera, OrbitCameraSmoothing, OrbitView};
+use bevy::prelude::*;
+use std::collections::HashMap;
+
+/// Names an `OrbitCameraTarget`, so that it can be found again when restoring an
+/// `OrbitCameraState`, e.g. in another run of the game
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct OrbitCameraTargetName(pub String);
+
+/// Everything needed to put an `OrbitCamera` back where it was
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct OrbitCameraState {
+    pub view: OrbitView,
+    /// The `OrbitCameraTargetName` of the target, if it had one
+    pub target: Option<String>,
+    /// How far the focus was panned away from the target
+    #[cfg_attr(feature = "serialize", serde(default))]
+    pub pan_offset: Vec3,
+}
+
+impl OrbitCameraState {
+    pub fn capture(
+        orbit_camera: &OrbitCamera,
+        target_query: &Query<(Entity, &OrbitCameraTargetName)>,
+    ) -> Self {
+        let target = orbit_camera.target.and_then(|target| {
+            target_query
+                .get::<OrbitCameraTargetName>(target)
+                .ok()
+                .map(|target_name| target_name.0.clone())
+        });
+        Self {
+            view: orbit_camera.view(),
+            target,
+            pan_offset: orbit_camera.pan_offset(),
+        }
+    }
+    /// Moves the camera to the view, and targets the entity with the target's name; if
+    /// there's no such entity, the camera is left without a target
+    pub fn restore(
+        &self,
+        orbit_camera: &mut OrbitCamera,
+        target_query: &mut Query<(Entity, &OrbitCameraTargetName)>,
+    ) {
+        self.restore_target(orbit_camera, self.find_target(target_query));
+        orbit_camera.cancel_animation().set_view(self.view);
+    }
+    /// As `restore`, but animates to the view
+    pub fn animate(
+        &self,
+        orbit_camera: &mut OrbitCamera,
+        target_query: &mut Query<(Entity, &OrbitCameraTargetName)>,
+        duration: f32,
+        easing: Easing,
+    ) {
+        self.restore_target(orbit_camera, self.find_target(target_query));
+        orbit_camera.animate_to(self.view, duration, easing);
+    }
+    /// Targets `target`, panned as it was when captured; the view is restored too, so the
+    /// target counts as acquired rather than having its preferences adopted over the view
+    pub(crate) fn restore_target(&self, orbit_camera: &mut OrbitCamera, target: Option<Entity>) {
+        orbit_camera.target = target;
+        orbit_camera.acquired = target;
+        // the offset only means anything from the same target
+        orbit_camera.pan_offset = match target {
+            Some(_) => self.pan_offset,
+            None => Vec3::zero(),
+        };
+    }
+    fn find_target(
+        &self,
+        target_query: &mut Query<(Entity, &OrbitCameraTargetName)>,
+    ) -> Option<Entity> {
+        self.target.as_ref().and_then(|name| {
+            target_query
+                .iter()
+                .iter()
+                .find(|(_, target_name)| &target_name.0 == name)
+                .map(|(entity, _)| entity)
+        })
+    }
+}
+
+/// Named views that can be saved and restored with `OrbitCameraBookmarkEvent`s
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct OrbitCameraBookmarks {
+    bookmarks: HashMap<String, OrbitCameraState>,
+}
+
+impl OrbitCameraBookmarks {
+    pub fn insert(&mut self, name: impl Into<String>, state: OrbitCameraState) {
+        self.bookmarks.insert(name.into(), state);
+    }
+    pub fn get(&self, name: &str) -> Option<&OrbitCameraState> {
+        self.bookmarks.get(name)
+    }
+    pub fn remove(&mut self, name: &str) -> Option<OrbitCameraState> {
+        self.bookmarks.remove(name)
+    }
+    pub fn names(&self) -> impl Iterator<Item = &String> {
+        self.bookmarks.keys()
+    }
+}
+
+/// How the camera gets to a restored bookmark
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BookmarkTransition {
+    /// Cut straight to it
+    Instant,
+    /// Ease there with the camera's `OrbitCameraSmoothing`, or cut if it has none
+    Smooth,
+    /// Animate there over `duration` seconds
+    Animated { duration: f32, easing: Easing },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum OrbitCameraBookmarkEvent {
+    /// Saves the camera's current state under the name, replacing any bookmark with that name
+    Save { camera: Entity, name: String },
+    /// Restores the camera to the named bookmark, if there is one
+    Restore {
+        camera: Entity,
+        name: String,
+        transition: BookmarkTransition,
+    },
+}
+
+// core
+pub fn bookmark_camera(
+    mut bookmark_event_reader: Local<EventReader<OrbitCameraBookmarkEvent>>,
+    bookmark_events: Res<Events<OrbitCameraBookmarkEvent>>,
+    mut bookmarks: ResMut<OrbitCameraBookmarks>,
+    mut camera_query: Query<(Entity, &mut OrbitCamera, Option<&mut OrbitCameraSmoothing>)>,
+    mut target_query: Query<(Entity, &OrbitCameraTargetName)>,
+) {
+    for event in bookmark_event_reader.iter(&bookmark_events) {
+        for (entity, mut orbit_camera, smoothing) in &mut camera_query.iter() {
+            match event {
+                OrbitCameraBookmarkEvent::Save { camera, name } if *camera == entity => {
+                    let state = OrbitCameraState::capture(&orbit_camera, &target_query);
+                    bookmarks.insert(name.clone(), state);
+                }
+                OrbitCameraBookmarkEvent::Restore {
+                    camera,
+                    name,
+                    transition,
+                } if *camera == entity => {
+                    if let Some(state) = bookmarks.get(name) {
+                        match *transition {
+                            BookmarkTransition::Instant => {
+                                state.restore(&mut orbit_camera, &mut target_query);
+                                if let Some(mut smoothing) = smoothing {
+                                    smoothing.reset();
+                                }
+                            }
+                            BookmarkTransition::Smooth => {
+                                state.restore(&mut orbit_camera, &mut target_query);
+                            }
+                            BookmarkTransition::Animated { duration, easing } => {
+                                state.animate(
+                                    &mut orbit_camera,
+                                    &mut target_query,
+                                    duration,
+                                    easing,
+                                );
+                            }
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::OrbitCameraTarget;
+
+    #[test]
+    fn restore_brings_back_the_pan_offset() {
+        let target = Entity::from_id(1);
+        let state = OrbitCameraState {
+            view: OrbitView {
+                focus: Vec3::new(1.0, 2.0, 3.0),
+                distance: 10.0,
+                pitch: 0.5,
+                yaw: 1.0,
+            },
+            target: Some("target".to_string()),
+            pan_offset: Vec3::new(0.0, 2.0, 0.0),
+        };
+        let mut orbit_camera = OrbitCamera::new(None, 20.0, 0.0, 0.0);
+        orbit_camera.target = Some(Entity::from_id(2));
+        orbit_camera.pan(Vec3::new(5.0, 0.0, 0.0));
+        state.restore_target(&mut orbit_camera, Some(target));
+        assert_eq!(orbit_camera.target, Some(target));
+        assert_eq!(orbit_camera.pan_offset(), state.pan_offset);
+    }
+
+    #[test]
+    fn restore_without_the_target_drops_the_pan_offset() {
+        let state = OrbitCameraState {
+            view: OrbitView {
+                focus: Vec3::zero(),
+                distance: 10.0,
+                pitch: 0.0,
+                yaw: 0.0,
+            },
+            target: Some("gone".to_string()),
+            pan_offset: Vec3::new(0.0, 2.0, 0.0),
+        };
+        let mut orbit_camera = OrbitCamera::new(Some(Entity::from_id(2)), 20.0, 0.0, 0.0);
+        orbit_camera.pan(Vec3::new(5.0, 0.0, 0.0));
+        state.restore_target(&mut orbit_camera, None);
+        assert_eq!(orbit_camera.target, None);
+        assert_eq!(orbit_camera.pan_offset(), Vec3::zero());
+    }
+
+    #[test]
+    fn restore_keeps_the_view_over_the_target_preferences() {
+        let target = Entity::from_id(1);
+        let state = OrbitCameraState {
+            view: OrbitView {
+                focus: Vec3::zero(),
+                distance: 30.0,
+                pitch: 0.5,
+                yaw: 0.0,
+            },
+            target: Some("target".to_string()),
+            pan_offset: Vec3::zero(),
+        };
+        let mut orbit_camera = OrbitCamera::new(None, 20.0, 0.0, 0.0);
+        state.restore_target(&mut orbit_camera, Some(target));
+        orbit_camera.set_view(state.view);
+        orbit_camera.acquire(
+            target,
+            &OrbitCameraTarget::default()
+                .with_distance(10.0)
+                .with_pitch(0.0),
+            1.0,
+            1.0,
+        );
+        assert_eq!(orbit_camera.distance(), 30.0);
+        assert_eq!(orbit_camera.pitch(), 0.5);
+    }
+}
@@ -0,0 +1,246 @@
+use bevy::prelude::*;
+
+/// Marks an entity as scene geometry that an `OrbitCamera` with an `OrbitCameraCollision`
+/// shouldn't pass behind; the shape is centred on the entity's translation
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OrbitCameraObstacle {
+    /// A box aligned to the world axes, ignoring the entity's rotation and scale
+    Aabb {
+        half_extents: Vec3,
+    },
+    Sphere {
+        radius: f32,
+    },
+}
+
+impl OrbitCameraObstacle {
+    /// Returns the distance along the ray at which it enters this obstacle, if it does,
+    /// where `direction` is normalized; rays starting inside the obstacle don't count
+    pub fn ray_entry(&self, center: Vec3, origin: Vec3, direction: Vec3) -> Option<f32> {
+        match *self {
+            OrbitCameraObstacle::Aabb { half_extents } => {
+                // slab method
+                let min: [f32; 3] = (center - half_extents).into();
+                let max: [f32; 3] = (center + half_extents).into();
+                let origin: [f32; 3] = origin.into();
+                let direction: [f32; 3] = direction.into();
+                let mut t_near = f32::NEG_INFINITY;
+                let mut t_far = f32::INFINITY;
+                for axis in 0..3 {
+                    let (origin, direction, min, max) =
+                        (origin[axis], direction[axis], min[axis], max[axis]);
+                    if direction.abs() < f32::EPSILON {
+                        if origin < min || origin > max {
+                            return None;
+                        }
+                    } else {
+                        let t1 = (min - origin) / direction;
+                        let t2 = (max - origin) / direction;
+                        t_near = t_near.max(t1.min(t2));
+                        t_far = t_far.min(t1.max(t2));
+                    }
+                }
+                if t_near > 0.0 && t_near <= t_far {
+                    Some(t_near)
+                } else {
+                    None
+                }
+            }
+            OrbitCameraObstacle::Sphere { radius } => {
+                let offset = origin - center;
+                let b = offset.dot(direction);
+                let c = offset.length_squared() - radius * radius;
+                let discriminant = b * b - c;
+                if c <= 0.0 || discriminant < 0.0 {
+                    return None;
+                }
+                let t = -b - discriminant.sqrt();
+                if t > 0.0 {
+                    Some(t)
+                } else {
+                    None
+                }
+            }
+        }
+    }
+}
+
+/// Add this to an `OrbitCamera` entity to pull the camera in towards the focus
+/// when an `OrbitCameraObstacle` blocks the view of it
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OrbitCameraCollision {
+    /// How far in front of an obstacle to keep the camera
+    pub padding: f32,
+    obstructed_distance: Option<f32>,
+}
+
+impl Default for OrbitCameraCollision {
+    fn default() -> Self {
+        Self::new(0.2)
+    }
+}
+
+impl OrbitCameraCollision {
+    pub fn new(padding: f32) -> Self {
+        Self {
+            padding,
+            obstructed_distance: None,
+        }
+    }
+    /// How far from the focus the camera can be before it's blocked, if it is
+    pub fn obstructed_distance(&self) -> Option<f32> {
+        self.obstructed_distance
+    }
+    /// Finds how far from `focus` towards `position` the camera can be before one of the
+    /// `obstacles`, given by their shapes and centres, is in the way
+    pub(crate) fn update_obstruction<I>(&mut self, focus: Vec3, position: Vec3, obstacles: I)
+    where
+        I: IntoIterator<Item = (OrbitCameraObstacle, Vec3)>,
+    {
+        let offset = position - focus;
+        let distance = offset.length();
+        if distance <= 0.0 {
+            self.obstructed_distance = None;
+            return;
+        }
+        let direction = offset / distance;
+        let nearest = obstacles
+            .into_iter()
+            .filter_map(|(obstacle, center)| obstacle.ray_entry(center, focus, direction))
+            .filter(|t| *t < distance)
+            .fold(None, |nearest: Option<f32>, t| {
+                Some(nearest.map_or(t, |nearest| nearest.min(t)))
+            });
+        self.obstructed_distance =
+            nearest.map(|nearest| (nearest - self.padding).max(f32::EPSILON));
+    }
+    /// Pulls `position` in towards `focus` if it's beyond the obstructed distance
+    pub fn constrain(&self, focus: Vec3, position: Vec3) -> Vec3 {
+        match self.obstructed_distance {
+            Some(obstructed_distance) => {
+                let offset = position - focus;
+                let distance = offset.length();
+                if distance > obstructed_distance && distance > 0.0 {
+                    focus + offset * (obstructed_distance / distance)
+                } else {
+                    position
+                }
+            }
+            None => position,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::assert_close;
+
+    fn assert_entry(entry: Option<f32>, expected: f32) {
+        assert_close(entry.expect("the ray should enter the obstacle"), expected);
+    }
+
+    #[test]
+    fn sphere_entry_from_outside() {
+        let sphere = OrbitCameraObstacle::Sphere { radius: 1.0 };
+        let entry = sphere.ray_entry(Vec3::zero(), Vec3::new(0.0, 0.0, 5.0), -Vec3::unit_z());
+        assert_entry(entry, 4.0);
+    }
+
+    #[test]
+    fn sphere_ignores_rays_starting_inside() {
+        let sphere = OrbitCameraObstacle::Sphere { radius: 1.0 };
+        let entry = sphere.ray_entry(Vec3::zero(), Vec3::new(0.0, 0.0, 0.5), -Vec3::unit_z());
+        assert_eq!(entry, None);
+    }
+
+    #[test]
+    fn sphere_behind_the_ray() {
+        let sphere = OrbitCameraObstacle::Sphere { radius: 1.0 };
+        let entry = sphere.ray_entry(Vec3::zero(), Vec3::new(0.0, 0.0, 5.0), Vec3::unit_z());
+        assert_eq!(entry, None);
+    }
+
+    #[test]
+    fn sphere_missed() {
+        let sphere = OrbitCameraObstacle::Sphere { radius: 1.0 };
+        let entry = sphere.ray_entry(Vec3::zero(), Vec3::new(2.0, 0.0, 5.0), -Vec3::unit_z());
+        assert_eq!(entry, None);
+    }
+
+    #[test]
+    fn aabb_entry_along_an_axis() {
+        let aabb = OrbitCameraObstacle::Aabb {
+            half_extents: Vec3::new(1.0, 2.0, 1.0),
+        };
+        let entry = aabb.ray_entry(Vec3::zero(), Vec3::new(0.5, 1.5, 5.0), -Vec3::unit_z());
+        assert_entry(entry, 4.0);
+    }
+
+    #[test]
+    fn aabb_missed_by_an_axis_parallel_ray() {
+        let aabb = OrbitCameraObstacle::Aabb {
+            half_extents: Vec3::new(1.0, 1.0, 1.0),
+        };
+        // parallel to the X and Y slabs, but outside the X slab
+        let entry = aabb.ray_entry(Vec3::zero(), Vec3::new(1.5, 0.0, 5.0), -Vec3::unit_z());
+        assert_eq!(entry, None);
+    }
+
+    #[test]
+    fn aabb_entry_diagonally() {
+        let aabb = OrbitCameraObstacle::Aabb {
+            half_extents: Vec3::new(1.0, 1.0, 1.0),
+        };
+        let direction = Vec3::new(-1.0, -1.0, -1.0).normalize();
+        let entry = aabb.ray_entry(Vec3::zero(), Vec3::new(5.0, 5.0, 5.0), direction);
+        assert_entry(entry, 4.0 * 3.0_f32.sqrt());
+    }
+
+    #[test]
+    fn aabb_ignores_rays_starting_inside() {
+        let aabb = OrbitCameraObstacle::Aabb {
+            half_extents: Vec3::new(1.0, 1.0, 1.0),
+        };
+        let entry = aabb.ray_entry(Vec3::zero(), Vec3::new(0.0, 0.0, 0.5), -Vec3::unit_z());
+        assert_eq!(entry, None);
+    }
+
+    #[test]
+    fn aabb_off_centre() {
+        let aabb = OrbitCameraObstacle::Aabb {
+            half_extents: Vec3::new(1.0, 1.0, 1.0),
+        };
+        let center = Vec3::new(10.0, 0.0, 0.0);
+        let entry = aabb.ray_entry(center, Vec3::new(10.0, 0.0, 5.0), -Vec3::unit_z());
+        assert_entry(entry, 4.0);
+        let entry = aabb.ray_entry(center, Vec3::new(0.0, 0.0, 5.0), -Vec3::unit_z());
+        assert_eq!(entry, None);
+    }
+
+    #[test]
+    fn constrain_pulls_in_only_past_the_obstruction() {
+        let mut collision = OrbitCameraCollision::new(0.2);
+        collision.obstructed_distance = Some(2.0);
+        let focus = Vec3::zero();
+        let constrained = collision.constrain(focus, Vec3::new(0.0, 0.0, 10.0));
+        assert_close(constrained, Vec3::new(0.0, 0.0, 2.0));
+        let unconstrained = collision.constrain(focus, Vec3::new(0.0, 0.0, 1.0));
+        assert_close(unconstrained, Vec3::new(0.0, 0.0, 1.0));
+    }
+
+    #[test]
+    fn obstruction_is_along_the_given_ray() {
+        let mut collision = OrbitCameraCollision::new(0.5);
+        let pillar = (
+            OrbitCameraObstacle::Sphere { radius: 1.0 },
+            Vec3::new(5.0, 0.0, 0.0),
+        );
+        // the pillar isn't between the focus and a camera behind it on z...
+        collision.update_obstruction(Vec3::zero(), Vec3::new(0.0, 0.0, 10.0), vec![pillar]);
+        assert_eq!(collision.obstructed_distance(), None);
+        // ...but is once the camera has eased round to x
+        collision.update_obstruction(Vec3::zero(), Vec3::new(10.0, 0.0, 0.0), vec![pillar]);
+        assert_close(collision.obstructed_distance().unwrap(), 3.5);
+    }
+}
@@ -0,0 +1,103 @@
+use bevy::prelude::*;
+
+/// How the focus of an `OrbitCamera` follows its target
+// https://catlikecoding.com/unity/tutorials/movement/orbit-camera/
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FollowPolicy {
+    /// The focus is always the target's position
+    Snap,
+    /// The focus only moves when the target leaves a sphere around it, and otherwise
+    /// slowly recentres on the target
+    Lazy {
+        /// The radius around the focus the target can move within without moving the focus
+        focus_radius: f32,
+        /// The fraction of the distance to the target, from 0 to 1, that the focus closes
+        /// each second while inside `focus_radius`; 0 doesn't recentre at all
+        focus_centering: f32,
+    },
+}
+
+impl Default for FollowPolicy {
+    fn default() -> Self {
+        FollowPolicy::Snap
+    }
+}
+
+impl FollowPolicy {
+    pub fn lazy(focus_radius: f32, focus_centering: f32) -> Self {
+        FollowPolicy::Lazy {
+            focus_radius: focus_radius.max(0.0),
+            focus_centering: focus_centering.max(0.0).min(1.0),
+        }
+    }
+    /// Returns the new focus, given the current focus and the target's position,
+    /// after `dt` seconds
+    pub fn follow(&self, focus: Vec3, target: Vec3, dt: f32) -> Vec3 {
+        match *self {
+            FollowPolicy::Snap => target,
+            FollowPolicy::Lazy {
+                focus_radius,
+                focus_centering,
+            } => {
+                let distance = (target - focus).length();
+                let mut t = 1.0;
+                if distance > 0.01 && focus_centering > 0.0 {
+                    t = (1.0 - focus_centering).powf(dt);
+                }
+                if distance > focus_radius {
+                    t = t.min(focus_radius / distance);
+                }
+                // t is how far to stay back from the target, towards the current focus
+                target.lerp(focus, t)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::assert_close;
+
+    #[test]
+    fn snap_goes_straight_to_the_target() {
+        let target = Vec3::new(3.0, 4.0, 5.0);
+        assert_close(FollowPolicy::Snap.follow(Vec3::zero(), target, 0.1), target);
+    }
+
+    #[test]
+    fn lazy_stays_put_inside_the_radius_without_centering() {
+        let lazy = FollowPolicy::lazy(2.0, 0.0);
+        let focus = Vec3::zero();
+        assert_close(lazy.follow(focus, Vec3::new(1.0, 0.0, 0.0), 1.0), focus);
+    }
+
+    #[test]
+    fn lazy_is_dragged_to_the_edge_of_the_radius() {
+        let lazy = FollowPolicy::lazy(2.0, 0.0);
+        let focus = lazy.follow(Vec3::zero(), Vec3::new(10.0, 0.0, 0.0), 1.0);
+        assert_close(focus, Vec3::new(8.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn lazy_recentres_by_the_centering_fraction_per_second() {
+        let lazy = FollowPolicy::lazy(2.0, 0.5);
+        let focus = lazy.follow(Vec3::zero(), Vec3::new(1.0, 0.0, 0.0), 1.0);
+        assert_close(focus, Vec3::new(0.5, 0.0, 0.0));
+        // two half-second steps close the same distance as one second
+        let half = lazy.follow(Vec3::zero(), Vec3::new(1.0, 0.0, 0.0), 0.5);
+        let twice = lazy.follow(half, Vec3::new(1.0, 0.0, 0.0), 0.5);
+        assert_close(twice, focus);
+    }
+
+    #[test]
+    fn lazy_clamps_its_parameters() {
+        assert_eq!(
+            FollowPolicy::lazy(-1.0, 2.0),
+            FollowPolicy::Lazy {
+                focus_radius: 0.0,
+                focus_centering: 1.0,
+            }
+        );
+    }
+}
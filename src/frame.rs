@@ -0,0 +1,245 @@
+use crate::{Easing, OrbitCamera, OrbitCameraTarget};
+use bevy::prelude::*;
+use bevy::render::camera::PerspectiveProjection;
+use bevy::render::mesh::{VertexAttribute, VertexAttributeValues};
+
+/// A box aligned to the world axes
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Aabb {
+    pub min: Vec3,
+    pub max: Vec3,
+}
+
+impl Aabb {
+    pub fn from_point(point: Vec3) -> Self {
+        Self {
+            min: point,
+            max: point,
+        }
+    }
+    /// The box around a sphere
+    pub fn from_sphere(center: Vec3, radius: f32) -> Self {
+        let half_extents = Vec3::new(radius, radius, radius);
+        Self {
+            min: center - half_extents,
+            max: center + half_extents,
+        }
+    }
+    /// The smallest box containing all the points, if there are any
+    pub fn from_points(points: impl IntoIterator<Item = Vec3>) -> Option<Self> {
+        points.into_iter().fold(None, |aabb: Option<Aabb>, point| {
+            Some(match aabb {
+                Some(aabb) => aabb.union(&Aabb::from_point(point)),
+                None => Aabb::from_point(point),
+            })
+        })
+    }
+    /// The box containing the mesh's vertices once moved by `transform`
+    pub fn from_mesh(mesh: &Mesh, transform: &Transform) -> Option<Self> {
+        let positions = mesh
+            .attributes
+            .iter()
+            .find(|attribute| attribute.name == VertexAttribute::POSITION)?;
+        let positions = match &positions.values {
+            VertexAttributeValues::Float3(positions) => positions,
+            _ => return None,
+        };
+        let local = Aabb::from_points(positions.iter().map(|position| Vec3::from(*position)))?;
+        Aabb::from_points(
+            local.corners().iter().map(|corner| {
+                transform.translation + transform.rotation * (transform.scale * *corner)
+            }),
+        )
+    }
+    pub fn union(&self, other: &Aabb) -> Self {
+        Self {
+            min: self.min.min(other.min),
+            max: self.max.max(other.max),
+        }
+    }
+    pub fn center(&self) -> Vec3 {
+        (self.min + self.max) / 2.0
+    }
+    pub fn corners(&self) -> [Vec3; 8] {
+        let (min, max) = (self.min, self.max);
+        [
+            Vec3::new(min.x(), min.y(), min.z()),
+            Vec3::new(max.x(), min.y(), min.z()),
+            Vec3::new(min.x(), max.y(), min.z()),
+            Vec3::new(max.x(), max.y(), min.z()),
+            Vec3::new(min.x(), min.y(), max.z()),
+            Vec3::new(max.x(), min.y(), max.z()),
+            Vec3::new(min.x(), max.y(), max.z()),
+            Vec3::new(max.x(), max.y(), max.z()),
+        ]
+    }
+    /// The radius of the sphere around the box, which is what gets framed so that the
+    /// box fits whichever way the camera is facing
+    pub fn radius(&self) -> f32 {
+        (self.max - self.min).length() / 2.0
+    }
+}
+
+/// Asks a camera to frame entities, using the `OrbitCameraTarget::radius` of targets that
+/// have one, and otherwise their meshes, or their translations if they have no mesh
+#[derive(Debug, Clone, PartialEq)]
+pub struct OrbitCameraFrameEvent {
+    pub camera: Entity,
+    /// The entities to frame; if empty, the camera's target is framed, and otherwise
+    /// the camera stops following its target unless it's the only entity
+    pub entities: Vec<Entity>,
+    /// How much room to leave around the entities, e.g. 1.1 for 10% either side
+    pub padding: f32,
+    /// How long to animate there for and how, or `None` to cut straight there
+    pub animation: Option<(f32, Easing)>,
+}
+
+impl OrbitCameraFrameEvent {
+    pub const DEFAULT_PADDING: f32 = 1.1;
+    /// Frames the camera's target, cutting straight there
+    pub fn target(camera: Entity) -> Self {
+        Self {
+            camera,
+            entities: Vec::new(),
+            padding: Self::DEFAULT_PADDING,
+            animation: None,
+        }
+    }
+    /// Frames the entities, cutting straight there
+    pub fn entities(camera: Entity, entities: Vec<Entity>) -> Self {
+        Self {
+            camera,
+            entities,
+            padding: Self::DEFAULT_PADDING,
+            animation: None,
+        }
+    }
+    pub fn with_padding(mut self, padding: f32) -> Self {
+        self.padding = padding;
+        self
+    }
+    pub fn with_animation(mut self, duration: f32, easing: Easing) -> Self {
+        self.animation = Some((duration, easing));
+        self
+    }
+}
+
+// core
+pub fn frame_camera(
+    mut frame_event_reader: Local<EventReader<OrbitCameraFrameEvent>>,
+    frame_events: Res<Events<OrbitCameraFrameEvent>>,
+    meshes: Res<Assets<Mesh>>,
+    mut camera_query: Query<(Entity, &mut OrbitCamera, Option<&PerspectiveProjection>)>,
+    bounds_query: Query<(
+        &Transform,
+        Option<&Handle<Mesh>>,
+        Option<&OrbitCameraTarget>,
+    )>,
+) {
+    for event in frame_event_reader.iter(&frame_events) {
+        for (entity, mut orbit_camera, perspective_projection) in &mut camera_query.iter() {
+            if entity != event.camera {
+                continue;
+            }
+            let entities: Vec<Entity> = if event.entities.is_empty() {
+                orbit_camera.target.into_iter().collect()
+            } else {
+                event.entities.clone()
+            };
+            let bounds = entities
+                .iter()
+                .filter_map(|entity| {
+                    let transform = bounds_query.get::<Transform>(*entity).ok()?;
+                    if let Ok(target) = bounds_query.get::<OrbitCameraTarget>(*entity) {
+                        if let Some(radius) = target.radius {
+                            let center = orbit_camera.target_point(&target, &transform);
+                            return Some(Aabb::from_sphere(center, radius));
+                        }
+                    }
+                    let mesh = bounds_query
+                        .get::<Handle<Mesh>>(*entity)
+                        .ok()
+                        .and_then(|handle| meshes.get(&handle));
+                    Some(
+                        mesh.and_then(|mesh| Aabb::from_mesh(mesh, &transform))
+                            .unwrap_or_else(|| Aabb::from_point(transform.translation)),
+                    )
+                })
+                .fold(None, |bounds: Option<Aabb>, aabb| {
+                    Some(bounds.map_or(aabb, |bounds| bounds.union(&aabb)))
+                });
+            let bounds = match bounds {
+                Some(bounds) => bounds,
+                None => continue,
+            };
+            let (fov, aspect_ratio) = perspective_projection.map_or_else(
+                || (PerspectiveProjection::default().fov, 1.0),
+                |p| (p.fov, p.aspect_ratio),
+            );
+            let framed = orbit_camera.framed(&bounds, fov, aspect_ratio, event.padding);
+            orbit_camera.fit_orthographic(bounds.radius() * event.padding, aspect_ratio);
+            match (orbit_camera.target, entities.as_slice()) {
+                // keep following the target, offset to the middle of its bounds
+                (Some(target), [entity]) if target == *entity => {
+                    if let (Ok(target_transform), Ok(target_info)) = (
+                        bounds_query.get::<Transform>(target),
+                        bounds_query.get::<OrbitCameraTarget>(target),
+                    ) {
+                        orbit_camera.pan_offset = framed.focus
+                            - orbit_camera.target_point(&target_info, &target_transform);
+                    }
+                }
+                _ => {
+                    orbit_camera.release_target();
+                }
+            }
+            match event.animation {
+                Some((duration, easing)) => {
+                    orbit_camera.animate_to(framed, duration, easing);
+                }
+                None => {
+                    orbit_camera.cancel_animation().set_view(framed);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::assert_close;
+
+    #[test]
+    fn radius_is_half_the_diagonal() {
+        let aabb = Aabb {
+            min: Vec3::new(-1.0, -1.0, -1.0),
+            max: Vec3::new(1.0, 1.0, 1.0),
+        };
+        assert_close(aabb.radius(), 3.0_f32.sqrt());
+        assert_eq!(Aabb::from_point(Vec3::new(1.0, 2.0, 3.0)).radius(), 0.0);
+    }
+
+    #[test]
+    fn from_points_contains_them_all() {
+        assert_eq!(Aabb::from_points(Vec::new()), None);
+        let aabb = Aabb::from_points(vec![
+            Vec3::new(1.0, -2.0, 0.0),
+            Vec3::new(-1.0, 3.0, 4.0),
+            Vec3::new(0.0, 0.0, -4.0),
+        ])
+        .unwrap();
+        assert_eq!(aabb.min, Vec3::new(-1.0, -2.0, -4.0));
+        assert_eq!(aabb.max, Vec3::new(1.0, 3.0, 4.0));
+        assert_eq!(aabb.center(), Vec3::new(0.0, 0.5, 0.0));
+    }
+
+    #[test]
+    fn union_contains_both() {
+        let a = Aabb::from_point(Vec3::new(-1.0, 0.0, 0.0));
+        let b = Aabb::from_point(Vec3::new(0.0, 2.0, 3.0));
+        let union = a.union(&b);
+        assert_eq!(union.min, Vec3::new(-1.0, 0.0, 0.0));
+        assert_eq!(union.max, Vec3::new(0.0, 2.0, 3.0));
+    }
+}
@@ -0,0 +1,92 @@
+use bevy::prelude::*;
+
+/// Makes an `OrbitCamera` follow the weighted centroid of several `OrbitCameraTarget`s
+/// instead of its `target`, e.g. to keep every player of a couch co-op game on screen
+#[derive(Debug, Clone, PartialEq)]
+pub struct OrbitCameraTargetGroup {
+    /// Each target and its weight; heavier targets pull the focus towards them, and targets
+    /// that have gone are left out
+    pub targets: Vec<(Entity, f32)>,
+    /// When set, the distance (or the scale, when orthographic) follows the group so that
+    /// every target stays in view, with this much room around them, e.g. 1.1 for 10% either
+    /// side; the distance limits still apply
+    pub fit_padding: Option<f32>,
+    /// How far around each target's focus to keep in view, unless it has a radius of its own
+    pub target_radius: f32,
+}
+
+impl Default for OrbitCameraTargetGroup {
+    fn default() -> Self {
+        Self {
+            targets: Vec::new(),
+            fit_padding: None,
+            target_radius: 1.0,
+        }
+    }
+}
+
+impl OrbitCameraTargetGroup {
+    pub fn new(targets: Vec<(Entity, f32)>) -> Self {
+        Self {
+            targets,
+            ..Default::default()
+        }
+    }
+    pub fn with_target(mut self, entity: Entity, weight: f32) -> Self {
+        self.targets.push((entity, weight));
+        self
+    }
+    pub fn with_fit_padding(mut self, padding: f32) -> Self {
+        self.fit_padding = Some(padding);
+        self
+    }
+    pub fn with_target_radius(mut self, radius: f32) -> Self {
+        self.target_radius = radius;
+        self
+    }
+    /// Sets a target's weight, adding it if it's not already in the group
+    pub fn set_weight(&mut self, entity: Entity, weight: f32) -> &mut Self {
+        match self
+            .targets
+            .iter_mut()
+            .find(|(target, _)| *target == entity)
+        {
+            Some((_, target_weight)) => *target_weight = weight,
+            None => self.targets.push((entity, weight)),
+        }
+        self
+    }
+    pub fn remove(&mut self, entity: Entity) -> &mut Self {
+        self.targets.retain(|(target, _)| *target != entity);
+        self
+    }
+    /// The weighted centroid of the targets' focuses, or `None` if the weights add up to zero
+    pub(crate) fn centroid(&self, targets: &[(Vec3, f32, Option<f32>)]) -> Option<Vec3> {
+        let total_weight = targets.iter().map(|(_, weight, _)| weight).sum::<f32>();
+        if total_weight <= 0.0 {
+            return None;
+        }
+        let weighted_sum = targets
+            .iter()
+            .fold(Vec3::zero(), |sum, (point, weight, _)| {
+                sum + *point * *weight
+            });
+        Some(weighted_sum / total_weight)
+    }
+    /// How far from `centroid` to keep in view, padded, or `None` if the distance doesn't
+    /// follow the group
+    pub(crate) fn radius(
+        &self,
+        centroid: Vec3,
+        targets: &[(Vec3, f32, Option<f32>)],
+    ) -> Option<f32> {
+        let padding = self.fit_padding?;
+        let furthest = targets
+            .iter()
+            .map(|(point, _, radius)| {
+                (*point - centroid).length() + radius.unwrap_or(self.target_radius)
+            })
+            .fold(0.0, f32::max);
+        Some(furthest * padding)
+    }
+}
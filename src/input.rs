@@ -0,0 +1,556 @@
+use crate::{
+    OrbitCamera, OrbitCameraFrameEvent, OrbitCameraObstacle, OrbitCameraSwitchTarget,
+    OrbitProjection, TargetOrder,
+};
+use bevy::input::mouse::{MouseMotion, MouseScrollUnit, MouseWheel};
+use bevy::prelude::*;
+use bevy::render::camera::{Camera, PerspectiveProjection};
+use bevy::window::{CursorMoved, WindowId, Windows};
+use std::f32::consts::PI;
+
+/// A mouse button or key that can be bound to an orbit camera action
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum InputButton {
+    Mouse(MouseButton),
+    Key(KeyCode),
+}
+
+/// Which modifier keys must be held, where either the left or right key counts
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Modifiers {
+    pub shift: bool,
+    pub ctrl: bool,
+    pub alt: bool,
+}
+
+impl Modifiers {
+    pub const NONE: Modifiers = Modifiers {
+        shift: false,
+        ctrl: false,
+        alt: false,
+    };
+    pub const SHIFT: Modifiers = Modifiers {
+        shift: true,
+        ctrl: false,
+        alt: false,
+    };
+    pub const CTRL: Modifiers = Modifiers {
+        shift: false,
+        ctrl: true,
+        alt: false,
+    };
+    pub const ALT: Modifiers = Modifiers {
+        shift: false,
+        ctrl: false,
+        alt: true,
+    };
+    /// The modifier keys currently held
+    pub fn pressed(keyboard_input: &Input<KeyCode>) -> Self {
+        Self {
+            shift: keyboard_input.pressed(KeyCode::LShift)
+                || keyboard_input.pressed(KeyCode::RShift),
+            ctrl: keyboard_input.pressed(KeyCode::LControl)
+                || keyboard_input.pressed(KeyCode::RControl),
+            alt: keyboard_input.pressed(KeyCode::LAlt) || keyboard_input.pressed(KeyCode::RAlt),
+        }
+    }
+}
+
+/// A button together with the exact modifiers that must be held with it
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Binding {
+    pub button: InputButton,
+    pub modifiers: Modifiers,
+}
+
+impl Binding {
+    pub fn new(button: InputButton, modifiers: Modifiers) -> Self {
+        Self { button, modifiers }
+    }
+    pub fn mouse(mouse_button: MouseButton) -> Self {
+        Self::new(InputButton::Mouse(mouse_button), Modifiers::NONE)
+    }
+    pub fn key(key_code: KeyCode) -> Self {
+        Self::new(InputButton::Key(key_code), Modifiers::NONE)
+    }
+    pub fn with_modifiers(mut self, modifiers: Modifiers) -> Self {
+        self.modifiers = modifiers;
+        self
+    }
+    /// Whether the button is held with exactly these modifiers
+    pub fn pressed(
+        &self,
+        keyboard_input: &Input<KeyCode>,
+        mouse_input: &Input<MouseButton>,
+    ) -> bool {
+        Modifiers::pressed(keyboard_input) == self.modifiers
+            && match self.button {
+                InputButton::Mouse(mouse_button) => mouse_input.pressed(mouse_button),
+                InputButton::Key(key_code) => keyboard_input.pressed(key_code),
+            }
+    }
+    /// Whether the button was pressed this frame with exactly these modifiers
+    pub fn just_pressed(
+        &self,
+        keyboard_input: &Input<KeyCode>,
+        mouse_input: &Input<MouseButton>,
+    ) -> bool {
+        Modifiers::pressed(keyboard_input) == self.modifiers
+            && match self.button {
+                InputButton::Mouse(mouse_button) => mouse_input.just_pressed(mouse_button),
+                InputButton::Key(key_code) => keyboard_input.just_pressed(key_code),
+            }
+    }
+}
+
+/// How far the camera rotates for a drag of the mouse
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OrbitSensitivity {
+    /// At 1.0, dragging across the whole width of the window turns 360 degrees
+    pub yaw: f32,
+    /// At 1.0, dragging across the whole height of the window turns 180 degrees
+    pub pitch: f32,
+    pub invert_yaw: bool,
+    pub invert_pitch: bool,
+}
+
+impl Default for OrbitSensitivity {
+    fn default() -> Self {
+        Self {
+            yaw: 1.0,
+            pitch: 1.0,
+            invert_yaw: false,
+            invert_pitch: false,
+        }
+    }
+}
+
+impl OrbitSensitivity {
+    /// Rotates the camera for a drag of `delta` pixels across a window of `window_size` pixels
+    pub(crate) fn rotate(&self, orbit_camera: &mut OrbitCamera, delta: Vec2, window_size: Vec2) {
+        let (yaw, pitch): (f32, f32) = delta.into();
+        let (width, height): (f32, f32) = window_size.into();
+        let yaw = if self.invert_yaw { -yaw } else { yaw } * self.yaw;
+        let pitch = if self.invert_pitch { -pitch } else { pitch } * self.pitch;
+        orbit_camera.add_yaw(-yaw * 2.0 * PI / width); // 360 degrees from left edge of window to right edge of window
+        orbit_camera.add_pitch(pitch * PI / height); // 180 degrees from bottom edge of window to top edge of window
+    }
+}
+
+/// What zooming moves towards
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ZoomMode {
+    /// Move towards the focus
+    Focus,
+    /// Move towards the point under the cursor, taking the focus along with it; the point
+    /// is on the target's `OrbitCameraObstacle` if the cursor is over it, and otherwise on
+    /// the ground plane, `ground_height` along the camera's up axis
+    Cursor { ground_height: f32 },
+}
+
+impl Default for ZoomMode {
+    fn default() -> Self {
+        ZoomMode::Focus
+    }
+}
+
+/// Maps the orbit camera actions to buttons; `None` disables an action
+///
+/// Dragging while `orbit` is held rotates, while `pan` is held pans, and while `zoom`
+/// is held zooms. `reset` returns the camera to its home view.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OrbitCameraInput {
+    pub orbit: Option<Binding>,
+    pub pan: Option<Binding>,
+    pub zoom: Option<Binding>,
+    /// Whether the mouse wheel zooms
+    pub zoom_wheel: bool,
+    /// How many pixels of scrolling on a trackpad count as one notch of a mouse wheel
+    pub scroll_pixels_per_notch: f32,
+    pub zoom_mode: ZoomMode,
+    pub reset: Option<Binding>,
+    /// Frames the target with an `OrbitCameraFrameEvent`
+    pub frame: Option<Binding>,
+    /// Switches to the next target in `target_order` with an `OrbitCameraSwitchTarget`
+    pub next_target: Option<Binding>,
+    /// Switches to the previous target in `target_order` with an `OrbitCameraSwitchTarget`
+    pub previous_target: Option<Binding>,
+    pub target_order: TargetOrder,
+    /// Switches between perspective and orthographic projections
+    pub toggle_projection: Option<Binding>,
+    pub orbit_sensitivity: OrbitSensitivity,
+}
+
+impl Default for OrbitCameraInput {
+    fn default() -> Self {
+        Self::blender()
+    }
+}
+
+impl OrbitCameraInput {
+    /// Orbit with the middle mouse button, pan with shift, zoom with ctrl, reset with home,
+    /// frame with numpad period, switch projections with numpad 5, and cycle targets with tab
+    pub fn blender() -> Self {
+        Self {
+            orbit: Some(Binding::mouse(MouseButton::Middle)),
+            pan: Some(Binding::mouse(MouseButton::Middle).with_modifiers(Modifiers::SHIFT)),
+            zoom: Some(Binding::mouse(MouseButton::Middle).with_modifiers(Modifiers::CTRL)),
+            zoom_wheel: true,
+            scroll_pixels_per_notch: 20.0,
+            zoom_mode: ZoomMode::default(),
+            reset: Some(Binding::key(KeyCode::Home)),
+            frame: Some(Binding::key(KeyCode::Decimal)),
+            next_target: Some(Binding::key(KeyCode::Tab)),
+            previous_target: Some(Binding::key(KeyCode::Tab).with_modifiers(Modifiers::SHIFT)),
+            target_order: TargetOrder::default(),
+            toggle_projection: Some(Binding::key(KeyCode::Numpad5)),
+            orbit_sensitivity: OrbitSensitivity::default(),
+        }
+    }
+    /// Hold alt, then orbit with the left, pan with the middle and zoom with the right
+    /// mouse button; reset with home, frame with F and cycle targets with tab
+    pub fn maya() -> Self {
+        Self {
+            orbit: Some(Binding::mouse(MouseButton::Left).with_modifiers(Modifiers::ALT)),
+            pan: Some(Binding::mouse(MouseButton::Middle).with_modifiers(Modifiers::ALT)),
+            zoom: Some(Binding::mouse(MouseButton::Right).with_modifiers(Modifiers::ALT)),
+            zoom_wheel: true,
+            scroll_pixels_per_notch: 20.0,
+            zoom_mode: ZoomMode::default(),
+            reset: Some(Binding::key(KeyCode::Home)),
+            frame: Some(Binding::key(KeyCode::F)),
+            next_target: Some(Binding::key(KeyCode::Tab)),
+            previous_target: Some(Binding::key(KeyCode::Tab).with_modifiers(Modifiers::SHIFT)),
+            target_order: TargetOrder::default(),
+            toggle_projection: None,
+            orbit_sensitivity: OrbitSensitivity::default(),
+        }
+    }
+    /// Orbit with alt and the left mouse button, pan with the middle mouse button, zoom
+    /// with alt and the right mouse button; reset with home, frame with F and cycle targets
+    /// with tab
+    pub fn unity() -> Self {
+        Self {
+            orbit: Some(Binding::mouse(MouseButton::Left).with_modifiers(Modifiers::ALT)),
+            pan: Some(Binding::mouse(MouseButton::Middle)),
+            zoom: Some(Binding::mouse(MouseButton::Right).with_modifiers(Modifiers::ALT)),
+            zoom_wheel: true,
+            scroll_pixels_per_notch: 20.0,
+            zoom_mode: ZoomMode::default(),
+            reset: Some(Binding::key(KeyCode::Home)),
+            frame: Some(Binding::key(KeyCode::F)),
+            next_target: Some(Binding::key(KeyCode::Tab)),
+            previous_target: Some(Binding::key(KeyCode::Tab).with_modifiers(Modifiers::SHIFT)),
+            target_order: TargetOrder::default(),
+            toggle_projection: None,
+            orbit_sensitivity: OrbitSensitivity::default(),
+        }
+    }
+    pub fn with_zoom_mode(mut self, zoom_mode: ZoomMode) -> Self {
+        self.zoom_mode = zoom_mode;
+        self
+    }
+    pub fn with_orbit_sensitivity(mut self, orbit_sensitivity: OrbitSensitivity) -> Self {
+        self.orbit_sensitivity = orbit_sensitivity;
+        self
+    }
+    fn active(
+        binding: &Option<Binding>,
+        keyboard_input: &Input<KeyCode>,
+        mouse_input: &Input<MouseButton>,
+    ) -> bool {
+        binding.map_or(false, |binding| {
+            binding.pressed(keyboard_input, mouse_input)
+        })
+    }
+}
+
+/// The window the cursor was last seen in, and where it was
+#[derive(Default)]
+pub struct CursorState {
+    cursor_moved_event_reader: EventReader<CursorMoved>,
+    cursor: Option<(WindowId, Vec2)>,
+}
+
+// default implementation
+pub fn zoom_camera(
+    mut mouse_wheel_event_reader: Local<EventReader<MouseWheel>>,
+    mut mouse_motion_event_reader: Local<EventReader<MouseMotion>>,
+    mut cursor_state: Local<CursorState>,
+    mouse_wheel_events: Res<Events<MouseWheel>>,
+    mouse_motion_events: Res<Events<MouseMotion>>,
+    cursor_moved_events: Res<Events<CursorMoved>>,
+    input: Res<OrbitCameraInput>,
+    keyboard_input: Res<Input<KeyCode>>,
+    mouse_input: Res<Input<MouseButton>>,
+    windows: Res<Windows>,
+    mut camera_query: Query<(
+        &mut OrbitCamera,
+        &Transform,
+        Option<&Camera>,
+        Option<&PerspectiveProjection>,
+    )>,
+    target_query: Query<(&OrbitCameraObstacle, &Transform)>,
+) {
+    const PIXELS_PER_NOTCH: f32 = 10.0; // how far dragging counts as one turn of the mouse wheel
+    let mut zoom = 0.0;
+    for event in mouse_wheel_event_reader.iter(&mouse_wheel_events) {
+        if input.zoom_wheel {
+            zoom += match event.unit {
+                MouseScrollUnit::Line => event.y,
+                MouseScrollUnit::Pixel => event.y / input.scroll_pixels_per_notch,
+            };
+        }
+    }
+    let dragging = OrbitCameraInput::active(&input.zoom, &keyboard_input, &mouse_input);
+    for event in mouse_motion_event_reader.iter(&mouse_motion_events) {
+        if dragging {
+            let (_, delta_y): (f32, f32) = event.delta.into();
+            zoom -= delta_y / PIXELS_PER_NOTCH;
+        }
+    }
+    let cursor_state = &mut *cursor_state;
+    if let Some(event) = cursor_state
+        .cursor_moved_event_reader
+        .latest(&cursor_moved_events)
+    {
+        cursor_state.cursor = Some((event.id, event.position));
+    }
+    if zoom == 0.0 {
+        return;
+    }
+    for (mut orbit_camera, camera_transform, camera, perspective_projection) in
+        &mut camera_query.iter()
+    {
+        let fov = perspective_projection.map_or(PerspectiveProjection::default().fov, |p| p.fov);
+        let projection = orbit_camera.projection();
+        let half_height = orbit_camera.view_half_height(fov);
+        orbit_camera.zoom(zoom);
+        let ground_height = match input.zoom_mode {
+            ZoomMode::Focus => continue,
+            ZoomMode::Cursor { ground_height } => ground_height,
+        };
+        let window_id = camera.map(|camera| camera.window);
+        let cursor = match cursor_state.cursor {
+            Some((cursor_window_id, cursor))
+                if window_id.map_or(true, |window_id| window_id == cursor_window_id) =>
+            {
+                cursor
+            }
+            _ => continue,
+        };
+        if half_height == orbit_camera.view_half_height(fov) {
+            continue;
+        }
+        let (origin, direction) = cursor_ray(
+            cursor,
+            window_size(&windows, window_id),
+            projection,
+            fov,
+            &camera_transform,
+        );
+        let target_hit = orbit_camera.target.and_then(|target| {
+            let obstacle = target_query.get::<OrbitCameraObstacle>(target).ok()?;
+            let target_transform = target_query.get::<Transform>(target).ok()?;
+            obstacle.ray_entry(target_transform.translation, origin, direction)
+        });
+        let up = orbit_camera.up_axis();
+        let ground_hit = if direction.dot(up).abs() > f32::EPSILON {
+            Some((ground_height - origin.dot(up)) / direction.dot(up)).filter(|t| *t > 0.0)
+        } else {
+            None
+        };
+        if let Some(t) = target_hit.or(ground_hit) {
+            let point = origin + direction * t;
+            // scaling the view about the point keeps it under the cursor
+            let scale = orbit_camera.view_half_height(fov) / half_height;
+            let offset = (point - orbit_camera.focus) * (1.0 - scale);
+            orbit_camera.pan(offset);
+        }
+    }
+}
+
+/// The origin and normalized direction in world space of the ray from the camera through
+/// the cursor, where the cursor is in pixels from the bottom left of the window
+pub(crate) fn cursor_ray(
+    cursor: Vec2,
+    window_size: Vec2,
+    projection: OrbitProjection,
+    fov: f32,
+    camera_transform: &Transform,
+) -> (Vec3, Vec3) {
+    let (x, y): (f32, f32) = (cursor / window_size * 2.0 - Vec2::one()).into();
+    let (width, height): (f32, f32) = window_size.into();
+    let rotation = camera_transform.rotation;
+    match projection {
+        OrbitProjection::Perspective => {
+            let half_height = (fov / 2.0).tan();
+            let half_width = half_height * width / height;
+            let direction = rotation * Vec3::new(x * half_width, y * half_height, -1.0);
+            (camera_transform.translation, direction.normalize())
+        }
+        OrbitProjection::Orthographic { scale } => {
+            let half_width = scale * width / height;
+            let offset = rotation * Vec3::new(x * half_width, y * scale, 0.0);
+            (
+                camera_transform.translation + offset,
+                rotation * -Vec3::unit_z(),
+            )
+        }
+    }
+}
+
+/// The size in pixels of the window, or of the primary window if there's no window
+pub(crate) fn window_size(windows: &Windows, window_id: Option<WindowId>) -> Vec2 {
+    window_id
+        .and_then(|window_id| windows.get(window_id))
+        .or_else(|| windows.get_primary())
+        .map(|window| Vec2::new(window.width as f32, window.height as f32))
+        .unwrap_or_else(|| Vec2::new(1280.0, 720.0))
+}
+
+// default implementation
+pub fn rotate_camera(
+    mut mouse_motion_event_reader: Local<EventReader<MouseMotion>>,
+    mouse_motion_events: Res<Events<MouseMotion>>,
+    input: Res<OrbitCameraInput>,
+    keyboard_input: Res<Input<KeyCode>>,
+    mouse_input: Res<Input<MouseButton>>,
+    windows: Res<Windows>,
+    mut camera_query: Query<(&mut OrbitCamera, Option<&Camera>)>,
+) {
+    let mut delta = Vec2::zero();
+    // always drain the events, so that motion from before the button was held is ignored
+    for event in mouse_motion_event_reader.iter(&mouse_motion_events) {
+        delta += event.delta;
+    }
+    if OrbitCameraInput::active(&input.orbit, &keyboard_input, &mouse_input) {
+        for (mut orbit_camera, camera) in &mut camera_query.iter() {
+            let window_size = window_size(&windows, camera.map(|camera| camera.window));
+            input
+                .orbit_sensitivity
+                .rotate(&mut orbit_camera, delta, window_size);
+        }
+    }
+}
+
+// default implementation
+pub fn pan_camera(
+    mut mouse_motion_event_reader: Local<EventReader<MouseMotion>>,
+    mouse_motion_events: Res<Events<MouseMotion>>,
+    input: Res<OrbitCameraInput>,
+    keyboard_input: Res<Input<KeyCode>>,
+    mouse_input: Res<Input<MouseButton>>,
+    windows: Res<Windows>,
+    mut camera_query: Query<(
+        &mut OrbitCamera,
+        Option<&Camera>,
+        Option<&PerspectiveProjection>,
+    )>,
+) {
+    let mut delta = Vec2::zero();
+    for event in mouse_motion_event_reader.iter(&mouse_motion_events) {
+        delta += event.delta;
+    }
+    if OrbitCameraInput::active(&input.pan, &keyboard_input, &mouse_input) {
+        for (mut orbit_camera, camera, perspective_projection) in &mut camera_query.iter() {
+            let window_size = window_size(&windows, camera.map(|camera| camera.window));
+            let fov =
+                perspective_projection.map_or(PerspectiveProjection::default().fov, |p| p.fov);
+            pan_by_pixels(&mut orbit_camera, delta, window_size, fov);
+        }
+    }
+}
+
+/// Pans the camera for a drag of `delta` pixels across a window of `window_size` pixels,
+/// so that whatever is at the focus moves with the cursor
+pub(crate) fn pan_by_pixels(
+    orbit_camera: &mut OrbitCamera,
+    delta: Vec2,
+    window_size: Vec2,
+    fov: f32,
+) {
+    let (delta_x, delta_y): (f32, f32) = delta.into();
+    let (_, height): (f32, f32) = window_size.into();
+    let units_per_pixel = 2.0 * orbit_camera.view_half_height(fov) / height;
+    let offset = orbit_camera.right() * -delta_x * units_per_pixel
+        + orbit_camera.up() * delta_y * units_per_pixel;
+    orbit_camera.pan(offset);
+}
+
+// default implementation
+pub fn reset_camera(
+    input: Res<OrbitCameraInput>,
+    keyboard_input: Res<Input<KeyCode>>,
+    mouse_input: Res<Input<MouseButton>>,
+    mut camera_query: Query<&mut OrbitCamera>,
+) {
+    if let Some(reset) = input.reset {
+        if reset.just_pressed(&keyboard_input, &mouse_input) {
+            for mut orbit_camera in &mut camera_query.iter() {
+                orbit_camera.reset();
+            }
+        }
+    }
+}
+
+// default implementation
+pub fn toggle_projection(
+    input: Res<OrbitCameraInput>,
+    keyboard_input: Res<Input<KeyCode>>,
+    mouse_input: Res<Input<MouseButton>>,
+    mut camera_query: Query<(&mut OrbitCamera, Option<&PerspectiveProjection>)>,
+) {
+    if let Some(toggle_projection) = input.toggle_projection {
+        if toggle_projection.just_pressed(&keyboard_input, &mouse_input) {
+            for (mut orbit_camera, perspective_projection) in &mut camera_query.iter() {
+                let fov =
+                    perspective_projection.map_or(PerspectiveProjection::default().fov, |p| p.fov);
+                orbit_camera.toggle_projection(fov);
+            }
+        }
+    }
+}
+
+// default implementation
+pub fn frame_target(
+    input: Res<OrbitCameraInput>,
+    keyboard_input: Res<Input<KeyCode>>,
+    mouse_input: Res<Input<MouseButton>>,
+    mut frame_events: ResMut<Events<OrbitCameraFrameEvent>>,
+    mut camera_query: Query<(Entity, &OrbitCamera)>,
+) {
+    if let Some(frame) = input.frame {
+        if frame.just_pressed(&keyboard_input, &mouse_input) {
+            for (entity, _) in &mut camera_query.iter() {
+                frame_events.send(OrbitCameraFrameEvent::target(entity));
+            }
+        }
+    }
+}
+
+// default implementation
+pub fn cycle_target(
+    input: Res<OrbitCameraInput>,
+    keyboard_input: Res<Input<KeyCode>>,
+    mouse_input: Res<Input<MouseButton>>,
+    mut switch_events: ResMut<Events<OrbitCameraSwitchTarget>>,
+    mut camera_query: Query<(Entity, &OrbitCamera)>,
+) {
+    let just_pressed = |binding: Option<Binding>| {
+        binding.map_or(false, |binding| {
+            binding.just_pressed(&keyboard_input, &mouse_input)
+        })
+    };
+    let switch: fn(Entity, TargetOrder) -> OrbitCameraSwitchTarget =
+        if just_pressed(input.next_target) {
+            OrbitCameraSwitchTarget::next
+        } else if just_pressed(input.previous_target) {
+            OrbitCameraSwitchTarget::previous
+        } else {
+            return;
+        };
+    for (entity, _) in &mut camera_query.iter() {
+        switch_events.send(switch(entity, input.target_order));
+    }
+}
@@ -0,0 +1,1513 @@
+#![feature(external_doc)]
+#![doc(include = "../README.md")]
+// #![allow(dead_code)]
+use bevy::app::stage;
+use bevy::prelude::*;
+use bevy::render::camera::PerspectiveProjection;
+use std::f32::consts::PI;
+use targeting::TargetCycle;
+
+mod animation;
+mod bookmarks;
+mod collision;
+mod follow;
+mod frame;
+mod group;
+mod input;
+mod projection;
+mod rate_input;
+mod smoothing;
+mod targeting;
+#[cfg(test)]
+mod test_util;
+mod touch;
+
+pub use animation::{animate_camera, Easing, OrbitAnimation, OrbitCameraAnimationFinished};
+pub use bookmarks::{
+    bookmark_camera, BookmarkTransition, OrbitCameraBookmarkEvent, OrbitCameraBookmarks,
+    OrbitCameraState, OrbitCameraTargetName,
+};
+pub use collision::{OrbitCameraCollision, OrbitCameraObstacle};
+pub use follow::FollowPolicy;
+pub use frame::{frame_camera, Aabb, OrbitCameraFrameEvent};
+pub use group::OrbitCameraTargetGroup;
+pub use input::{
+    cycle_target, frame_target, pan_camera, reset_camera, rotate_camera, toggle_projection,
+    zoom_camera, Binding, InputButton, Modifiers, OrbitCameraInput, OrbitSensitivity, ZoomMode,
+};
+pub use projection::{update_projection, OrbitProjection};
+pub use rate_input::{
+    gamepad_camera, keyboard_camera, AxisResponse, GamepadBindings, GamepadInput, KeyboardBindings,
+    OrbitCameraRateInput,
+};
+pub use smoothing::{OrbitCameraSmoothing, Spring};
+pub use targeting::{
+    switch_target, OrbitCameraSwitchTarget, OrbitCameraTargetLost, OrbitCameraTargetSwitched,
+    SwitchTo, TargetLossPolicy, TargetOrder,
+};
+pub use touch::{touch_camera, OrbitCameraTouchInput};
+
+// core
+const Y_AXIS: Vec3 = Vec3::unit_y();
+
+/// Adds the default orbit camera systems: mouse zoom, rotation, panning, reset, framing,
+/// target cycling and projection switching as configured by the `OrbitCameraInput` resource,
+/// keyboard and gamepad controls as configured by the `OrbitCameraRateInput` resource, touch
+/// gestures as configured by the `OrbitCameraTouchInput` resource, bookmarks, target
+/// switching and tracking, animations, obstacle avoidance, and moving the camera's `Transform`
+/// and projection to match its `OrbitCamera`
+pub struct OrbitCameraPlugin;
+
+impl Plugin for OrbitCameraPlugin {
+    fn build(&self, app: &mut AppBuilder) {
+        app.init_resource::<OrbitCameraInput>()
+            .init_resource::<OrbitCameraRateInput>()
+            .init_resource::<OrbitCameraTouchInput>()
+            .init_resource::<OrbitCameraBookmarks>()
+            .add_event::<OrbitCameraBookmarkEvent>()
+            .add_event::<OrbitCameraAnimationFinished>()
+            .add_event::<OrbitCameraFrameEvent>()
+            .add_event::<OrbitCameraTargetLost>()
+            .add_event::<OrbitCameraSwitchTarget>()
+            .add_event::<OrbitCameraTargetSwitched>()
+            .add_system(zoom_camera.system())
+            .add_system(rotate_camera.system())
+            .add_system(pan_camera.system())
+            .add_system(reset_camera.system())
+            .add_system(frame_target.system())
+            .add_system(cycle_target.system())
+            .add_system(toggle_projection.system())
+            .add_system(keyboard_camera.system())
+            .add_system(gamepad_camera.system())
+            .add_system(touch_camera.system())
+            .add_system(bookmark_camera.system())
+            .add_system(frame_camera.system())
+            .add_system(switch_target.system())
+            .add_system(update_camera.system())
+            .add_system(animate_camera.system())
+            .add_system(move_camera.system())
+            .add_system_to_stage(stage::POST_UPDATE, update_projection.system());
+    }
+}
+
+// core
+/// Marks an entity that cameras can target, with how it likes to be framed; cameras adopt
+/// the preferences it has when they acquire it
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct OrbitCameraTarget {
+    /// Higher priority targets come first when switching targets by `TargetOrder::Priority`
+    pub priority: i32,
+    /// The distance cameras move to
+    pub distance: Option<f32>,
+    /// The pitch cameras move to
+    pub pitch: Option<f32>,
+    /// Where cameras focus on it, in place of their own `target_offset`
+    pub offset: Option<TargetOffset>,
+    /// How far around its focus it reaches, which cameras fit on screen if there's no
+    /// preferred distance, and which framing and target groups keep in view
+    pub radius: Option<f32>,
+}
+
+impl OrbitCameraTarget {
+    pub fn with_priority(mut self, priority: i32) -> Self {
+        self.priority = priority;
+        self
+    }
+    pub fn with_distance(mut self, distance: f32) -> Self {
+        self.distance = Some(distance);
+        self
+    }
+    pub fn with_pitch(mut self, pitch: f32) -> Self {
+        self.pitch = Some(pitch);
+        self
+    }
+    pub fn with_offset(mut self, offset: TargetOffset) -> Self {
+        self.offset = Some(offset);
+        self
+    }
+    pub fn with_radius(mut self, radius: f32) -> Self {
+        self.radius = Some(radius);
+        self
+    }
+}
+
+// core
+pub struct OrbitCamera {
+    /// Which entity the camera is targeting, unless it has an `OrbitCameraTargetGroup`
+    pub target: Option<Entity>,
+    /// What point in the world the camera was last facing or should be facing
+    pub focus: Vec3,
+    /// How the focus follows the target
+    pub follow: FollowPolicy,
+    /// Where on the target to focus, relative to its translation, unless the target has
+    /// an offset of its own
+    pub target_offset: TargetOffset,
+    /// The target whose preferences were last adopted
+    acquired: Option<Entity>,
+    /// What to do when the target is lost
+    pub on_target_lost: TargetLossPolicy,
+    /// Whether the target has been lost and `on_target_lost` applied, so that it's only
+    /// applied once
+    target_lost: bool,
+    /// The order the camera is cycling through targets in
+    target_cycle: TargetCycle,
+    /// What the yaw and pitch are measured from
+    pub reference: OrbitReference,
+    /// How the camera swings back behind the target when it's not being turned, if it
+    /// turns with the target
+    pub realign: Option<Realign>,
+    /// Whether panning lets go of the target, or offsets the focus from it
+    pub pan_mode: PanMode,
+    /// How the distance changes when zooming
+    pub zoom_curve: ZoomCurve,
+    /// Whether the camera renders in perspective or orthographically
+    projection: OrbitProjection,
+    /// The field of view the projection was last switched with, which maps the distance
+    /// limits to limits on the orthographic scale
+    fov: f32,
+    /// How far the focus has been panned away from the target
+    pan_offset: Vec3,
+    /// Whether the camera is following an `OrbitCameraTargetGroup` rather than its target
+    following_group: bool,
+    /// The distance the camera should be from the entity it is targeting
+    distance: f32,
+    /// The minimum distance away from the target, must be more than 0
+    min_distance: f32,
+    /// The maximum distance away from the target, must be more than `min_distance`
+    max_distance: f32,
+    /// The pitch, or, the angle in radians from the XZ plane in the Y-axis direction
+    pitch: f32,
+    /// The minimum pitch, must be at least `MIN_PITCH`
+    min_pitch: f32,
+    /// The maximum pitch, must be more than `min_pitch` and at most `MAX_PITCH`
+    max_pitch: f32,
+    /// The yaw, or, the angle in radians from the positive Z-axis, or from behind the target
+    /// if it turns with the target
+    yaw: f32,
+    /// The minimum and maximum yaw, or `None` if the yaw is unbounded and wraps around
+    yaw_limits: Option<(f32, f32)>,
+    /// The roll, or, the angle in radians the camera is tilted about the direction it faces
+    roll: f32,
+    /// How the camera levels itself out once it hasn't been rolled for a while, if at all
+    pub auto_level: Option<AutoLevel>,
+    /// Whether the roll has been set since the last update
+    rolled: bool,
+    /// How long since the roll was last set
+    roll_idle_time: f32,
+    /// The view to return to on `reset`
+    home: OrbitView,
+    /// The animation in progress, if any
+    animation: Option<OrbitAnimation>,
+    /// The rotation from the default frame, where Y is up, to the one the camera orbits in
+    orbit_frame: Quat,
+    /// Whether the camera turns like a turntable or a trackball
+    mode: OrbitMode,
+    /// The camera's rotation within the orbit frame, kept only by a trackball
+    orientation: Quat,
+    /// The yaw and pitch the camera's own are measured from, updated from the target
+    reference_angles: (f32, f32),
+    /// Whether the yaw or pitch has been set since the last update
+    turned: bool,
+    /// How long since the yaw or pitch was last set
+    idle_time: f32,
+}
+
+/// What panning does to an `OrbitCamera` with a target
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PanMode {
+    /// Stop following the target, leaving the focus wherever it was panned to
+    Detach,
+    /// Keep following the target, with the focus offset by however far it was panned
+    Offset,
+}
+
+impl Default for PanMode {
+    fn default() -> Self {
+        PanMode::Offset
+    }
+}
+
+/// Where an `OrbitCamera` focuses on its target, e.g. on a character's head rather than
+/// at their feet
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TargetOffset {
+    /// Offset along the world axes
+    World(Vec3),
+    /// Offset along the target's own axes, so that it turns with the target
+    Local(Vec3),
+}
+
+impl Default for TargetOffset {
+    fn default() -> Self {
+        TargetOffset::World(Vec3::zero())
+    }
+}
+
+impl TargetOffset {
+    /// The point to focus on for a target with this transform
+    pub fn apply(&self, target_transform: &Transform) -> Vec3 {
+        match *self {
+            TargetOffset::World(offset) => target_transform.translation + offset,
+            TargetOffset::Local(offset) => {
+                target_transform.translation + target_transform.rotation * offset
+            }
+        }
+    }
+}
+
+/// What the yaw and pitch of an `OrbitCamera` are measured from; a camera without a single
+/// target, such as one with an `OrbitCameraTargetGroup`, measures them from the world
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OrbitReference {
+    /// The world axes
+    World,
+    /// The direction the target faces across the XZ plane, so that a yaw of 0 stays
+    /// behind it as it turns, e.g. behind a vehicle
+    TargetHeading,
+    /// The target's rotation, so that the pitch also follows it up and down slopes
+    TargetRotation,
+}
+
+impl Default for OrbitReference {
+    fn default() -> Self {
+        OrbitReference::World
+    }
+}
+
+impl OrbitReference {
+    /// The yaw and pitch of the point behind a target with this rotation, within the
+    /// camera's orbit frame
+    fn angles(&self, target_rotation: Quat, orbit_frame: Quat) -> (f32, f32) {
+        // the target faces the negative Z-axis, so behind it is the positive Z-axis
+        let behind = orbit_frame.conjugate() * (target_rotation * Vec3::unit_z());
+        match *self {
+            OrbitReference::World => (0.0, 0.0),
+            OrbitReference::TargetHeading => (behind.x().atan2(behind.z()), 0.0),
+            OrbitReference::TargetRotation => (
+                behind.x().atan2(behind.z()),
+                behind.y().max(-1.0).min(1.0).asin(),
+            ),
+        }
+    }
+}
+
+/// How an `OrbitCamera` turns
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OrbitMode {
+    /// Yaws around the up axis and pitches up to just short of the poles, so the camera
+    /// always stays upright
+    Turntable,
+    /// Turns about the camera's own axes without limits, so that it can tumble over the
+    /// poles and see underneath; the pitch and yaw limits and the `reference` are ignored
+    Trackball,
+}
+
+impl Default for OrbitMode {
+    fn default() -> Self {
+        OrbitMode::Turntable
+    }
+}
+
+/// Swings an `OrbitCamera` that turns with its target back behind it, once it hasn't been
+/// turned for a while
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Realign {
+    /// Seconds to wait after the camera was last turned
+    pub delay: f32,
+    /// Radians per second to swing the yaw back to 0
+    pub speed: f32,
+}
+
+impl Default for Realign {
+    fn default() -> Self {
+        Self {
+            delay: 1.0,
+            speed: PI / 2.0,
+        }
+    }
+}
+
+/// Rolls an `OrbitCamera` back to level once it hasn't been rolled for a while
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AutoLevel {
+    /// Seconds to wait after the camera was last rolled
+    pub delay: f32,
+    /// Radians per second to roll back to 0
+    pub speed: f32,
+}
+
+impl Default for AutoLevel {
+    fn default() -> Self {
+        Self {
+            delay: 0.5,
+            speed: PI / 2.0,
+        }
+    }
+}
+
+/// How the distance of an `OrbitCamera` changes for each notch of zooming in
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ZoomCurve {
+    /// Moves `speed` units closer
+    Linear { speed: f32 },
+    /// Moves `rate`, between 0 and 1, of the current distance closer, which feels the same
+    /// at any distance; use `ZoomCurve::exponential` to keep the rate in range
+    Exponential { rate: f32 },
+    /// Takes the current distance and the number of notches, and returns the new distance
+    Custom(fn(f32, f32) -> f32),
+}
+
+impl Default for ZoomCurve {
+    fn default() -> Self {
+        ZoomCurve::Linear { speed: 1.0 }
+    }
+}
+
+impl ZoomCurve {
+    /// An exponential curve, with `rate` kept above 0 and below 1
+    pub fn exponential(rate: f32) -> Self {
+        ZoomCurve::Exponential {
+            rate: Self::clamp_rate(rate),
+        }
+    }
+    // at 1 or more, zooming out is infinite and fractional notches are NaN
+    fn clamp_rate(rate: f32) -> f32 {
+        rate.max(f32::EPSILON).min(1.0 - f32::EPSILON)
+    }
+    /// Returns the distance after zooming in by `notches`, which is negative to zoom out
+    pub fn apply(&self, distance: f32, notches: f32) -> f32 {
+        match *self {
+            ZoomCurve::Linear { speed } => distance - speed * notches,
+            ZoomCurve::Exponential { rate } => {
+                distance * (1.0 - Self::clamp_rate(rate)).powf(notches)
+            }
+            ZoomCurve::Custom(curve) => curve(distance, notches),
+        }
+    }
+}
+
+/// The focus, distance, pitch and yaw of an `OrbitCamera`
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct OrbitView {
+    pub focus: Vec3,
+    pub distance: f32,
+    pub pitch: f32,
+    pub yaw: f32,
+}
+
+// core
+impl OrbitCamera {
+    pub const DEFAULT_MIN_DISTANCE: f32 = 5.0;
+    pub const DEFAULT_MAX_DISTANCE: f32 = 100.0;
+    pub const MAX_PITCH: f32 = 89.9 / 180.0 * PI; // 89.9 degrees
+    pub const MIN_PITCH: f32 = -Self::MAX_PITCH; // -89.9 degrees
+    const MAX_YAW: f32 = PI; // 180 degrees
+    const MIN_YAW: f32 = -Self::MAX_YAW; // -180 degrees
+    /// Creates a camera with the default limits; use `OrbitCamera::builder` to configure them
+    pub fn new(target: Option<Entity>, distance: f32, pitch: f32, yaw: f32) -> Self {
+        let mut orbit_camera = Self {
+            target,
+            focus: Vec3::default(),
+            follow: FollowPolicy::default(),
+            target_offset: TargetOffset::default(),
+            acquired: None,
+            on_target_lost: TargetLossPolicy::default(),
+            target_lost: false,
+            target_cycle: TargetCycle::default(),
+            reference: OrbitReference::default(),
+            realign: None,
+            pan_mode: PanMode::default(),
+            pan_offset: Vec3::zero(),
+            following_group: false,
+            zoom_curve: ZoomCurve::default(),
+            projection: OrbitProjection::default(),
+            fov: PerspectiveProjection::default().fov,
+            distance,
+            min_distance: Self::DEFAULT_MIN_DISTANCE,
+            max_distance: Self::DEFAULT_MAX_DISTANCE,
+            pitch,
+            min_pitch: Self::MIN_PITCH,
+            max_pitch: Self::MAX_PITCH,
+            yaw,
+            yaw_limits: None,
+            roll: 0.0,
+            auto_level: None,
+            rolled: false,
+            roll_idle_time: 0.0,
+            home: OrbitView {
+                focus: Vec3::default(),
+                distance,
+                pitch,
+                yaw,
+            },
+            animation: None,
+            orbit_frame: Quat::identity(),
+            mode: OrbitMode::default(),
+            orientation: Quat::identity(),
+            reference_angles: (0.0, 0.0),
+            turned: false,
+            idle_time: 0.0,
+        };
+        orbit_camera
+            .set_distance(distance)
+            .set_pitch(pitch)
+            .set_yaw(yaw);
+        orbit_camera.home = orbit_camera.view();
+        orbit_camera
+    }
+    pub fn builder() -> OrbitCameraBuilder {
+        OrbitCameraBuilder::default()
+    }
+    pub fn set_focus(&mut self, focus: Vec3) -> &mut Self {
+        self.focus = focus;
+        self
+    }
+    pub fn distance(&self) -> f32 {
+        self.distance
+    }
+    pub fn set_distance(&mut self, distance: f32) -> &mut Self {
+        self.distance = distance
+            .max(f32::EPSILON) // until I know otherwise, this should be sufficiently positive
+            .max(self.min_distance)
+            .min(self.max_distance);
+        self
+    }
+    pub fn add_distance(&mut self, distance: f32) -> &mut Self {
+        self.set_distance(self.distance() + distance);
+        self
+    }
+    /// Multiplies the distance, or the scale when orthographic, by `factor`
+    pub fn zoom_by(&mut self, factor: f32) -> &mut Self {
+        match self.projection {
+            OrbitProjection::Perspective => self.set_distance(self.distance * factor),
+            OrbitProjection::Orthographic { scale } => self.set_scale(scale * factor),
+        }
+    }
+    /// Zooms in by `notches` along the `zoom_curve`, or out if negative, by changing the
+    /// distance in perspective or the scale when orthographic; zero notches don't zoom at
+    /// all, so custom curves are only called with some input
+    pub fn zoom(&mut self, notches: f32) -> &mut Self {
+        if notches == 0.0 {
+            return self;
+        }
+        match self.projection {
+            OrbitProjection::Perspective => {
+                self.set_distance(self.zoom_curve.apply(self.distance, notches))
+            }
+            OrbitProjection::Orthographic { scale } => {
+                self.set_scale(self.zoom_curve.apply(scale, notches))
+            }
+        }
+    }
+    pub fn distance_limits(&self) -> (f32, f32) {
+        (self.min_distance, self.max_distance)
+    }
+    /// Sets the minimum and maximum distance, and clamps the current distance to them
+    pub fn set_distance_limits(
+        &mut self,
+        min: f32,
+        max: f32,
+    ) -> Result<&mut Self, OrbitCameraError> {
+        Self::validate_distance_limits(min, max)?;
+        self.min_distance = min;
+        self.max_distance = max;
+        self.set_distance(self.distance);
+        if let OrbitProjection::Orthographic { scale } = self.projection {
+            self.set_scale(scale);
+        }
+        Ok(self)
+    }
+    pub fn projection(&self) -> OrbitProjection {
+        self.projection
+    }
+    /// Switches to a perspective projection with the field of view `fov`, moving to the
+    /// distance that keeps the view at the focus about the same size
+    pub fn set_perspective(&mut self, fov: f32) -> &mut Self {
+        self.fov = fov;
+        if let OrbitProjection::Orthographic { scale } = self.projection {
+            self.projection = OrbitProjection::Perspective;
+            self.set_distance(scale / (fov / 2.0).tan());
+        }
+        self
+    }
+    /// Switches to an orthographic projection, with the scale that keeps the view at the
+    /// focus about the same size as it was with the field of view `fov`
+    pub fn set_orthographic(&mut self, fov: f32) -> &mut Self {
+        if let OrbitProjection::Perspective = self.projection {
+            self.fov = fov;
+            self.set_scale(self.view_half_height(fov));
+        }
+        self
+    }
+    pub fn toggle_projection(&mut self, fov: f32) -> &mut Self {
+        match self.projection {
+            OrbitProjection::Perspective => self.set_orthographic(fov),
+            OrbitProjection::Orthographic { .. } => self.set_perspective(fov),
+        }
+    }
+    /// The smallest and largest orthographic scale, which are the view half heights at the
+    /// distance limits with the field of view the projection was last switched with
+    pub fn scale_limits(&self) -> (f32, f32) {
+        let half_fov_tan = (self.fov / 2.0).tan();
+        (
+            self.min_distance * half_fov_tan,
+            self.max_distance * half_fov_tan,
+        )
+    }
+    /// Sets the orthographic scale, clamped to `scale_limits`
+    fn set_scale(&mut self, scale: f32) -> &mut Self {
+        let (min, max) = self.scale_limits();
+        self.projection = OrbitProjection::Orthographic {
+            scale: scale.max(min).min(max),
+        };
+        self
+    }
+    /// Half the height of the view at the focus in world units, given the field of view
+    /// `fov` when in perspective
+    pub fn view_half_height(&self, fov: f32) -> f32 {
+        match self.projection {
+            OrbitProjection::Perspective => (fov / 2.0).tan() * self.distance,
+            OrbitProjection::Orthographic { scale } => scale,
+        }
+    }
+    pub fn pitch(&self) -> f32 {
+        self.pitch
+    }
+    pub fn set_pitch(&mut self, pitch: f32) -> &mut Self {
+        self.pitch = pitch.max(self.min_pitch).min(self.max_pitch);
+        self.turned = true;
+        if self.mode == OrbitMode::Trackball {
+            self.orientation = Self::orientation_from_angles(self.pitch, self.yaw);
+        }
+        self
+    }
+    pub fn add_pitch(&mut self, pitch: f32) -> &mut Self {
+        match self.mode {
+            OrbitMode::Turntable => self.set_pitch(self.pitch() + pitch),
+            OrbitMode::Trackball => self.turn_rolled(Quat::from_rotation_x(-pitch)),
+        }
+    }
+    pub fn pitch_limits(&self) -> (f32, f32) {
+        (self.min_pitch, self.max_pitch)
+    }
+    /// Sets the minimum and maximum pitch, and clamps the current pitch to them
+    pub fn set_pitch_limits(&mut self, min: f32, max: f32) -> Result<&mut Self, OrbitCameraError> {
+        Self::validate_pitch_limits(min, max)?;
+        self.min_pitch = min;
+        self.max_pitch = max;
+        self.set_pitch(self.pitch);
+        Ok(self)
+    }
+    pub fn yaw(&self) -> f32 {
+        self.yaw
+    }
+    pub fn set_yaw(&mut self, yaw: f32) -> &mut Self {
+        self.yaw = match self.yaw_limits {
+            Some((min, max)) => yaw.max(min).min(max),
+            None => Self::wrap(yaw, Self::MIN_YAW, Self::MAX_YAW),
+        };
+        self.turned = true;
+        if self.mode == OrbitMode::Trackball {
+            self.orientation = Self::orientation_from_angles(self.pitch, self.yaw);
+        }
+        self
+    }
+    pub fn add_yaw(&mut self, yaw: f32) -> &mut Self {
+        match self.mode {
+            OrbitMode::Turntable => self.set_yaw(self.yaw() + yaw),
+            OrbitMode::Trackball => self.turn_rolled(Quat::from_rotation_y(yaw)),
+        }
+    }
+    pub fn yaw_limits(&self) -> Option<(f32, f32)> {
+        self.yaw_limits
+    }
+    pub fn roll(&self) -> f32 {
+        self.roll
+    }
+    /// Tilts the camera about the direction it faces; positive values tilt the top of the
+    /// view to the left, and it wraps around at 180 degrees
+    pub fn set_roll(&mut self, roll: f32) -> &mut Self {
+        self.roll = Self::wrap(roll, -PI, PI);
+        self.rolled = true;
+        self
+    }
+    pub fn add_roll(&mut self, roll: f32) -> &mut Self {
+        self.set_roll(self.roll() + roll)
+    }
+    /// Restricts the yaw to an arc, e.g. `(-FRAC_PI_3, FRAC_PI_3)` for 60 degrees either side
+    /// of the positive Z-axis, and clamps the current yaw to it
+    pub fn set_yaw_limits(&mut self, min: f32, max: f32) -> Result<&mut Self, OrbitCameraError> {
+        Self::validate_yaw_limits(min, max)?;
+        self.yaw_limits = Some((min, max));
+        self.set_yaw(self.yaw);
+        Ok(self)
+    }
+    /// Lets the yaw rotate freely, wrapping around at 180 degrees
+    pub fn set_unbounded_yaw(&mut self) -> &mut Self {
+        self.yaw_limits = None;
+        self.set_yaw(self.yaw);
+        self
+    }
+    pub fn view(&self) -> OrbitView {
+        OrbitView {
+            focus: self.focus,
+            distance: self.distance,
+            pitch: self.pitch,
+            yaw: self.yaw,
+        }
+    }
+    /// Moves to the view, clamping it to the limits
+    pub fn set_view(&mut self, view: OrbitView) -> &mut Self {
+        self.set_focus(view.focus)
+            .set_distance(view.distance)
+            .set_pitch(view.pitch)
+            .set_yaw(view.yaw)
+    }
+    pub fn home(&self) -> OrbitView {
+        self.home
+    }
+    pub fn set_home(&mut self, home: OrbitView) -> &mut Self {
+        self.home = home;
+        self
+    }
+    /// Returns to the home view, which is the view the camera was created with unless
+    /// changed with `set_home`; the focus will be overridden while there's a target
+    pub fn reset(&mut self) -> &mut Self {
+        self.pan_offset = Vec3::zero();
+        self.animation = None;
+        self.set_roll(0.0).set_view(self.home)
+    }
+    /// Moves from the current view to `view` over `duration` seconds, replacing any
+    /// animation in progress; the yaw turns the shortest way around if it's unbounded, and
+    /// the focus heads for the target instead of `view.focus` while there's a target
+    pub fn animate_to(&mut self, view: OrbitView, duration: f32, easing: Easing) -> &mut Self {
+        self.animation = Some(OrbitAnimation::new(self.view(), view, duration, easing));
+        self
+    }
+    pub fn animation(&self) -> Option<&OrbitAnimation> {
+        self.animation.as_ref()
+    }
+    /// Stops the animation in progress where it is, without sending an
+    /// `OrbitCameraAnimationFinished`
+    pub fn cancel_animation(&mut self) -> &mut Self {
+        self.animation = None;
+        self
+    }
+    /// The view from the current pitch and yaw that fits `bounds` on screen, given the field
+    /// of view and aspect ratio of the perspective projection, where a `padding` above 1
+    /// leaves room around them
+    pub fn framed(&self, bounds: &Aabb, fov: f32, aspect_ratio: f32, padding: f32) -> OrbitView {
+        OrbitView {
+            focus: bounds.center(),
+            distance: Self::fit_distance(bounds.radius() * padding, fov, aspect_ratio),
+            pitch: self.pitch,
+            yaw: self.yaw,
+        }
+    }
+    /// Moves to the `framed` view, and fits the scale to `bounds` when orthographic
+    pub fn frame(&mut self, bounds: &Aabb, fov: f32, aspect_ratio: f32, padding: f32) -> &mut Self {
+        let framed = self.framed(bounds, fov, aspect_ratio, padding);
+        self.fit_orthographic(bounds.radius() * padding, aspect_ratio)
+            .set_view(framed)
+    }
+    /// The distance at which a sphere of `radius` around the focus just fits on screen
+    pub(crate) fn fit_distance(radius: f32, fov: f32, aspect_ratio: f32) -> f32 {
+        let half_fov_y = fov / 2.0;
+        let half_fov_x = (half_fov_y.tan() * aspect_ratio).atan();
+        radius / half_fov_y.min(half_fov_x).sin()
+    }
+    /// Where to focus on a target with this transform
+    pub(crate) fn target_point(&self, target: &OrbitCameraTarget, transform: &Transform) -> Vec3 {
+        target.offset.unwrap_or(self.target_offset).apply(transform)
+    }
+    /// Adopts the target's preferred distance and pitch, or heads for them if animating,
+    /// unless it's already been acquired
+    pub(crate) fn acquire(
+        &mut self,
+        entity: Entity,
+        target: &OrbitCameraTarget,
+        fov: f32,
+        aspect_ratio: f32,
+    ) {
+        if self.acquired == Some(entity) {
+            return;
+        }
+        self.acquired = Some(entity);
+        let distance = target.distance.or_else(|| {
+            target.radius.map(|radius| {
+                let radius = radius * OrbitCameraFrameEvent::DEFAULT_PADDING;
+                self.fit_orthographic(radius, aspect_ratio);
+                Self::fit_distance(radius, fov, aspect_ratio)
+            })
+        });
+        match &mut self.animation {
+            Some(animation) => {
+                animation.to.distance = distance.unwrap_or(animation.to.distance);
+                animation.to.pitch = target.pitch.unwrap_or(animation.to.pitch);
+            }
+            None => {
+                if let Some(distance) = distance {
+                    self.set_distance(distance);
+                }
+                if let Some(pitch) = target.pitch {
+                    self.set_pitch(pitch);
+                }
+            }
+        }
+    }
+    /// Fits the scale to a sphere of `radius` around the focus if orthographic
+    pub(crate) fn fit_orthographic(&mut self, radius: f32, aspect_ratio: f32) -> &mut Self {
+        if let OrbitProjection::Orthographic { .. } = self.projection {
+            self.set_scale(radius / aspect_ratio.min(1.0));
+        }
+        self
+    }
+    /// Lets go of the target, so that its preferences are adopted again if it's targeted again
+    pub(crate) fn release_target(&mut self) -> &mut Self {
+        self.target = None;
+        self.acquired = None;
+        self.pan_offset = Vec3::zero();
+        self.target_cycle = TargetCycle::default();
+        self
+    }
+    pub fn pan_offset(&self) -> Vec3 {
+        self.pan_offset
+    }
+    /// Moves the focus by `offset`, following `pan_mode` if there's a target; a target
+    /// group can't be detached from, so it's always offset
+    pub fn pan(&mut self, offset: Vec3) -> &mut Self {
+        if self.following_group {
+            self.pan_offset += offset;
+        } else if self.target.is_some() {
+            match self.pan_mode {
+                PanMode::Detach => {
+                    self.release_target();
+                }
+                PanMode::Offset => self.pan_offset += offset,
+            }
+        }
+        self.focus += offset;
+        self
+    }
+    pub fn position(&self) -> Vec3 {
+        self.focus + self.orbit_frame * (self.orientation() * Vec3::unit_z()) * self.distance
+    }
+    /// The direction to the right of the camera, which is perpendicular to the up axis
+    /// unless it's a trackball
+    pub fn right(&self) -> Vec3 {
+        self.orbit_frame * (self.orientation() * Vec3::unit_x())
+    }
+    /// The direction upwards from the camera, perpendicular to the direction it faces
+    pub fn up(&self) -> Vec3 {
+        self.orbit_frame * (self.orientation() * Y_AXIS)
+    }
+    pub fn mode(&self) -> OrbitMode {
+        self.mode
+    }
+    /// Switches between turning like a turntable and a trackball; a trackball starts from
+    /// the current view, and a turntable levels the camera out again
+    pub fn set_mode(&mut self, mode: OrbitMode) -> &mut Self {
+        match mode {
+            OrbitMode::Turntable => {
+                self.mode = mode;
+                self.set_pitch(self.pitch).set_yaw(self.yaw)
+            }
+            OrbitMode::Trackball => {
+                let orientation = self.orientation() * Quat::from_rotation_z(-self.roll);
+                self.mode = mode;
+                self.turn(orientation)
+            }
+        }
+    }
+    /// Which way is up for `look_at`: the up axis for a turntable, which stays upright, and
+    /// the camera's own up for a trackball
+    pub(crate) fn look_up(&self) -> Vec3 {
+        match self.mode {
+            // rolling the up axis about the way the camera faces rolls the camera by as much
+            OrbitMode::Turntable => {
+                let backwards = self.orbit_frame * (self.orientation() * Vec3::unit_z());
+                Quat::from_axis_angle(backwards, self.roll) * self.up_axis()
+            }
+            OrbitMode::Trackball => self.up(),
+        }
+    }
+    /// The camera's rotation within the orbit frame, from facing along the negative Z-axis,
+    /// including its roll
+    pub fn orientation(&self) -> Quat {
+        let orientation = match self.mode {
+            OrbitMode::Turntable => {
+                Self::orientation_from_angles(self.world_pitch(), self.world_yaw())
+            }
+            OrbitMode::Trackball => self.orientation,
+        };
+        orientation * Quat::from_rotation_z(self.roll)
+    }
+    fn orientation_from_angles(pitch: f32, yaw: f32) -> Quat {
+        Quat::from_rotation_y(yaw) * Quat::from_rotation_x(-pitch)
+    }
+    /// Turns a trackball by `rotation` about the axes of the view as it appears, i.e. rolled
+    fn turn_rolled(&mut self, rotation: Quat) -> &mut Self {
+        let roll = Quat::from_rotation_z(self.roll);
+        self.turn(self.orientation * roll * rotation * roll.conjugate())
+    }
+    /// Turns a trackball to `orientation`, keeping the pitch and yaw in step with it
+    fn turn(&mut self, orientation: Quat) -> &mut Self {
+        self.orientation = orientation.normalize();
+        let backwards = self.orientation * Vec3::unit_z();
+        self.pitch = backwards.y().max(-1.0).min(1.0).asin();
+        self.yaw = backwards.x().atan2(backwards.z());
+        self.turned = true;
+        self
+    }
+    /// The rotation from the default frame, where Y is up, to the one the camera orbits in
+    pub fn orbit_frame(&self) -> Quat {
+        self.orbit_frame
+    }
+    /// Orbits within the frame, e.g. one that tilts with the ground of a planet, so that the
+    /// yaw is measured from the frame's Z-axis and the pitch from its XZ plane
+    pub fn set_orbit_frame(&mut self, orbit_frame: Quat) -> &mut Self {
+        self.orbit_frame = orbit_frame.normalize();
+        self
+    }
+    /// The direction the camera orbits around and keeps upright to
+    pub fn up_axis(&self) -> Vec3 {
+        self.orbit_frame * Y_AXIS
+    }
+    /// Orbits around `up`, e.g. `Vec3::unit_z()` for a world where Z is up, by turning the
+    /// orbit frame the shortest way from Y being up
+    pub fn set_up_axis(&mut self, up: Vec3) -> &mut Self {
+        self.set_orbit_frame(Self::orbit_frame_from_up_axis(up))
+    }
+    fn orbit_frame_from_up_axis(up: Vec3) -> Quat {
+        let up = up.normalize();
+        let axis = Y_AXIS.cross(up);
+        if axis.length_squared() > f32::EPSILON {
+            Quat::from_axis_angle(axis.normalize(), Y_AXIS.dot(up).max(-1.0).min(1.0).acos())
+        } else if Y_AXIS.dot(up) > 0.0 {
+            Quat::identity()
+        } else {
+            Quat::from_rotation_x(PI)
+        }
+    }
+    /// The yaw from the orbit frame's positive Z-axis, whatever the `reference`
+    pub fn world_yaw(&self) -> f32 {
+        if self.mode == OrbitMode::Trackball {
+            return self.yaw;
+        }
+        Self::wrap(
+            self.yaw + self.reference_angles.0,
+            Self::MIN_YAW,
+            Self::MAX_YAW,
+        )
+    }
+    /// The pitch from the orbit frame's XZ plane, whatever the `reference`
+    pub fn world_pitch(&self) -> f32 {
+        if self.mode == OrbitMode::Trackball {
+            return self.pitch;
+        }
+        (self.pitch + self.reference_angles.1)
+            .max(Self::MIN_PITCH)
+            .min(Self::MAX_PITCH)
+    }
+    /// Measures the yaw and pitch from the target's rotation, if the camera turns with it,
+    /// and realigns the camera behind it once it's been left alone long enough
+    pub(crate) fn update_reference(
+        &mut self,
+        target_rotation: Option<Quat>,
+        delta_seconds: f32,
+    ) -> &mut Self {
+        self.reference_angles = match target_rotation {
+            Some(target_rotation) => self.reference.angles(target_rotation, self.orbit_frame),
+            None => (0.0, 0.0),
+        };
+        if self.turned || self.animation.is_some() {
+            self.idle_time = 0.0;
+        } else {
+            self.idle_time += delta_seconds;
+        }
+        self.turned = false;
+        let turns_with_target = target_rotation.is_some()
+            && self.reference != OrbitReference::World
+            && self.mode == OrbitMode::Turntable;
+        if let Some(realign) = self.realign {
+            if turns_with_target && self.idle_time >= realign.delay {
+                let behind = match self.yaw_limits {
+                    Some((min, max)) => 0.0_f32.max(min).min(max),
+                    None => 0.0,
+                };
+                let step = realign.speed * delta_seconds;
+                // set directly, as the camera hasn't been turned by anyone
+                self.yaw += (behind - self.yaw).max(-step).min(step);
+            }
+        }
+        self
+    }
+    /// Rolls the camera back to level once it's been left alone long enough, if it levels
+    /// itself out
+    pub(crate) fn update_level(&mut self, delta_seconds: f32) -> &mut Self {
+        if self.rolled {
+            self.roll_idle_time = 0.0;
+        } else {
+            self.roll_idle_time += delta_seconds;
+        }
+        self.rolled = false;
+        if let Some(auto_level) = self.auto_level {
+            if self.roll_idle_time >= auto_level.delay {
+                let step = auto_level.speed * delta_seconds;
+                // set directly, as the camera hasn't been rolled by anyone
+                self.roll -= self.roll.max(-step).min(step);
+            }
+        }
+        self
+    }
+    fn validate_distance_limits(min: f32, max: f32) -> Result<(), OrbitCameraError> {
+        if min > 0.0 && min < max {
+            Ok(())
+        } else {
+            Err(OrbitCameraError::InvalidDistanceLimits { min, max })
+        }
+    }
+    fn validate_pitch_limits(min: f32, max: f32) -> Result<(), OrbitCameraError> {
+        if min >= Self::MIN_PITCH && max <= Self::MAX_PITCH && min < max {
+            Ok(())
+        } else {
+            Err(OrbitCameraError::InvalidPitchLimits { min, max })
+        }
+    }
+    fn validate_yaw_limits(min: f32, max: f32) -> Result<(), OrbitCameraError> {
+        if min < max && max - min <= 2.0 * PI {
+            Ok(())
+        } else {
+            Err(OrbitCameraError::InvalidYawLimits { min, max })
+        }
+    }
+    pub(crate) fn wrap(num: f32, min: f32, max: f32) -> f32 {
+        if num < min {
+            // TODO: (maybe) turn this into a loop rather than recursive
+            Self::wrap(max - (min - num), min, max)
+        } else if num > max {
+            // TODO: (maybe) turn this into a loop rather than recursive
+            Self::wrap(min - (max - num), min, max)
+        } else {
+            num
+        }
+    }
+    // this should maybe be part of bevy or glam
+    pub(crate) fn calculate_relative_position(pitch: f32, yaw: f32, distance: f32) -> Vec3 {
+        // https://stackoverflow.com/questions/52781607/3d-point-from-two-angles-and-a-distance
+        let point = Vec3::new(
+            yaw.sin() * pitch.cos(),
+            pitch.sin(),
+            yaw.cos() * pitch.cos(),
+        );
+        assert!(point.is_normalized());
+        point * distance
+    }
+}
+
+/// Builds an `OrbitCamera` with custom limits, validating them in `build`
+pub struct OrbitCameraBuilder {
+    target: Option<Entity>,
+    focus: Vec3,
+    follow: FollowPolicy,
+    target_offset: TargetOffset,
+    on_target_lost: TargetLossPolicy,
+    reference: OrbitReference,
+    realign: Option<Realign>,
+    orbit_frame: Quat,
+    mode: OrbitMode,
+    auto_level: Option<AutoLevel>,
+    pan_mode: PanMode,
+    zoom_curve: ZoomCurve,
+    projection: OrbitProjection,
+    distance: f32,
+    distance_limits: (f32, f32),
+    pitch: f32,
+    pitch_limits: (f32, f32),
+    yaw: f32,
+    yaw_limits: Option<(f32, f32)>,
+}
+
+impl Default for OrbitCameraBuilder {
+    fn default() -> Self {
+        Self {
+            target: None,
+            focus: Vec3::default(),
+            follow: FollowPolicy::default(),
+            target_offset: TargetOffset::default(),
+            on_target_lost: TargetLossPolicy::default(),
+            reference: OrbitReference::default(),
+            realign: None,
+            orbit_frame: Quat::identity(),
+            mode: OrbitMode::default(),
+            auto_level: None,
+            pan_mode: PanMode::default(),
+            zoom_curve: ZoomCurve::default(),
+            projection: OrbitProjection::default(),
+            distance: OrbitCamera::DEFAULT_MIN_DISTANCE,
+            distance_limits: (
+                OrbitCamera::DEFAULT_MIN_DISTANCE,
+                OrbitCamera::DEFAULT_MAX_DISTANCE,
+            ),
+            pitch: 0.0,
+            pitch_limits: (OrbitCamera::MIN_PITCH, OrbitCamera::MAX_PITCH),
+            yaw: 0.0,
+            yaw_limits: None,
+        }
+    }
+}
+
+impl OrbitCameraBuilder {
+    pub fn target(mut self, target: Entity) -> Self {
+        self.target = Some(target);
+        self
+    }
+    pub fn focus(mut self, focus: Vec3) -> Self {
+        self.focus = focus;
+        self
+    }
+    pub fn follow(mut self, follow: FollowPolicy) -> Self {
+        self.follow = follow;
+        self
+    }
+    pub fn target_offset(mut self, target_offset: TargetOffset) -> Self {
+        self.target_offset = target_offset;
+        self
+    }
+    pub fn on_target_lost(mut self, on_target_lost: TargetLossPolicy) -> Self {
+        self.on_target_lost = on_target_lost;
+        self
+    }
+    pub fn reference(mut self, reference: OrbitReference) -> Self {
+        self.reference = reference;
+        self
+    }
+    pub fn realign(mut self, realign: Realign) -> Self {
+        self.realign = Some(realign);
+        self
+    }
+    pub fn orbit_frame(mut self, orbit_frame: Quat) -> Self {
+        self.orbit_frame = orbit_frame;
+        self
+    }
+    /// As `OrbitCamera::set_up_axis`
+    pub fn up_axis(mut self, up: Vec3) -> Self {
+        self.orbit_frame = OrbitCamera::orbit_frame_from_up_axis(up);
+        self
+    }
+    pub fn mode(mut self, mode: OrbitMode) -> Self {
+        self.mode = mode;
+        self
+    }
+    pub fn auto_level(mut self, auto_level: AutoLevel) -> Self {
+        self.auto_level = Some(auto_level);
+        self
+    }
+    pub fn pan_mode(mut self, pan_mode: PanMode) -> Self {
+        self.pan_mode = pan_mode;
+        self
+    }
+    pub fn zoom_curve(mut self, zoom_curve: ZoomCurve) -> Self {
+        self.zoom_curve = zoom_curve;
+        self
+    }
+    pub fn projection(mut self, projection: OrbitProjection) -> Self {
+        self.projection = projection;
+        self
+    }
+    pub fn distance(mut self, distance: f32) -> Self {
+        self.distance = distance;
+        self
+    }
+    pub fn distance_limits(mut self, min: f32, max: f32) -> Self {
+        self.distance_limits = (min, max);
+        self
+    }
+    pub fn pitch(mut self, pitch: f32) -> Self {
+        self.pitch = pitch;
+        self
+    }
+    pub fn pitch_limits(mut self, min: f32, max: f32) -> Self {
+        self.pitch_limits = (min, max);
+        self
+    }
+    pub fn yaw(mut self, yaw: f32) -> Self {
+        self.yaw = yaw;
+        self
+    }
+    pub fn yaw_limits(mut self, min: f32, max: f32) -> Self {
+        self.yaw_limits = Some((min, max));
+        self
+    }
+    pub fn unbounded_yaw(mut self) -> Self {
+        self.yaw_limits = None;
+        self
+    }
+    /// Validates the limits and clamps the distance, pitch and yaw to them
+    pub fn build(self) -> Result<OrbitCamera, OrbitCameraError> {
+        let mut orbit_camera = OrbitCamera::new(self.target, self.distance, self.pitch, self.yaw);
+        orbit_camera.set_focus(self.focus);
+        orbit_camera.follow = self.follow;
+        orbit_camera.target_offset = self.target_offset;
+        orbit_camera.on_target_lost = self.on_target_lost;
+        orbit_camera.reference = self.reference;
+        orbit_camera.realign = self.realign;
+        orbit_camera.auto_level = self.auto_level;
+        orbit_camera.set_orbit_frame(self.orbit_frame);
+        orbit_camera.pan_mode = self.pan_mode;
+        orbit_camera.zoom_curve = self.zoom_curve;
+        let (min, max) = self.distance_limits;
+        orbit_camera.set_distance_limits(min, max)?;
+        // after the limits, which the scale is clamped to
+        if let OrbitProjection::Orthographic { scale } = self.projection {
+            orbit_camera.set_scale(scale);
+        }
+        let (min, max) = self.pitch_limits;
+        orbit_camera.set_pitch_limits(min, max)?;
+        if let Some((min, max)) = self.yaw_limits {
+            orbit_camera.set_yaw_limits(min, max)?;
+        }
+        // re-apply the requested values, as `new` clamped them to the default limits
+        orbit_camera
+            .set_distance(self.distance)
+            .set_pitch(self.pitch)
+            .set_yaw(self.yaw)
+            .set_mode(self.mode);
+        orbit_camera.home = orbit_camera.view();
+        Ok(orbit_camera)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OrbitCameraError {
+    /// The minimum distance must be more than 0 and less than the maximum distance
+    InvalidDistanceLimits { min: f32, max: f32 },
+    /// The minimum pitch must be less than the maximum pitch, and both must be within
+    /// `OrbitCamera::MIN_PITCH` and `OrbitCamera::MAX_PITCH`
+    InvalidPitchLimits { min: f32, max: f32 },
+    /// The minimum yaw must be less than the maximum yaw, and the arc at most 360 degrees
+    InvalidYawLimits { min: f32, max: f32 },
+}
+
+impl std::fmt::Display for OrbitCameraError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OrbitCameraError::InvalidDistanceLimits { min, max } => write!(
+                f,
+                "invalid distance limits: expected 0 < min < max, got min {} and max {}",
+                min, max
+            ),
+            OrbitCameraError::InvalidPitchLimits { min, max } => write!(
+                f,
+                "invalid pitch limits: expected {} <= min < max <= {}, got min {} and max {}",
+                OrbitCamera::MIN_PITCH,
+                OrbitCamera::MAX_PITCH,
+                min,
+                max
+            ),
+            OrbitCameraError::InvalidYawLimits { min, max } => write!(
+                f,
+                "invalid yaw limits: expected min < max within one turn, got min {} and max {}",
+                min, max
+            ),
+        }
+    }
+}
+
+impl std::error::Error for OrbitCameraError {}
+
+// core
+pub fn update_camera(
+    time: Res<Time>,
+    mut target_lost_events: ResMut<Events<OrbitCameraTargetLost>>,
+    mut camera_query: Query<(
+        Entity,
+        &mut OrbitCamera,
+        Option<&OrbitCameraTargetGroup>,
+        Option<&PerspectiveProjection>,
+    )>,
+    mut target_query: Query<(Entity, &OrbitCameraTarget, &Transform)>,
+) {
+    for (entity, mut orbit_camera, group, perspective_projection) in &mut camera_query.iter() {
+        let (fov, aspect_ratio) = perspective_projection.map_or_else(
+            || (PerspectiveProjection::default().fov, 1.0),
+            |p| (p.fov, p.aspect_ratio),
+        );
+        orbit_camera.following_group = group.is_some();
+        let mut group_radius = None;
+        let mut target_rotation = None;
+        let target_point = match group {
+            Some(group) => {
+                let targets = group
+                    .targets
+                    .iter()
+                    .filter_map(|(entity, weight)| {
+                        let transform = target_query.get::<Transform>(*entity).ok()?;
+                        let target = target_query.get::<OrbitCameraTarget>(*entity).ok()?;
+                        Some((
+                            orbit_camera.target_point(&target, &transform),
+                            *weight,
+                            target.radius,
+                        ))
+                    })
+                    .collect::<Vec<_>>();
+                group.centroid(&targets).map(|centroid| {
+                    group_radius = group.radius(centroid, &targets);
+                    centroid
+                })
+            }
+            None => match orbit_camera.target {
+                Some(target_entity) => {
+                    let target_transform = target_query
+                        .get::<Transform>(target_entity)
+                        .ok()
+                        .map(|target_transform| target_transform.clone());
+                    let target = target_query
+                        .get::<OrbitCameraTarget>(target_entity)
+                        .ok()
+                        .map(|target| *target);
+                    match (target_transform, target) {
+                        (Some(target_transform), Some(target)) => {
+                            orbit_camera.target_lost = false;
+                            orbit_camera.acquire(target_entity, &target, fov, aspect_ratio);
+                            target_rotation = Some(target_transform.rotation);
+                            Some(orbit_camera.target_point(&target, &target_transform))
+                        }
+                        _ if !orbit_camera.target_lost => {
+                            let on_target_lost = orbit_camera.on_target_lost;
+                            let new_target = on_target_lost.apply(
+                                &mut orbit_camera,
+                                target_entity,
+                                &mut target_query,
+                            );
+                            // a camera still holding on to its target mustn't lose it again
+                            orbit_camera.target_lost = new_target == Some(target_entity);
+                            target_lost_events.send(OrbitCameraTargetLost {
+                                camera: entity,
+                                target: target_entity,
+                                new_target,
+                            });
+                            None
+                        }
+                        _ => None,
+                    }
+                }
+                None => {
+                    orbit_camera.acquired = None;
+                    None
+                }
+            },
+        };
+        orbit_camera
+            .update_reference(target_rotation, time.delta_seconds)
+            .update_level(time.delta_seconds);
+        let target_point = match target_point {
+            Some(target_point) => target_point,
+            None => continue,
+        };
+        let target_focus = target_point + orbit_camera.pan_offset;
+        if let Some(animation) = &mut orbit_camera.animation {
+            // the animation moves the focus, so that it eases onto the target
+            animation.to.focus = target_focus;
+            continue;
+        }
+        orbit_camera.focus =
+            orbit_camera
+                .follow
+                .follow(orbit_camera.focus, target_focus, time.delta_seconds);
+        if let Some(radius) = group_radius {
+            let distance = OrbitCamera::fit_distance(radius, fov, aspect_ratio);
+            orbit_camera
+                .fit_orthographic(radius, aspect_ratio)
+                .set_distance(distance);
+        }
+    }
+}
+
+// core
+// This "snaps" unless the camera has an `OrbitCameraSmoothing`, which works for an editor
+// TODO: make this lazier i.e. "translation" changes only when the position
+// has deviated from the by some distance (the focus can already be lazy, see `FollowPolicy`)
+// TODO: The rest of this: https://catlikecoding.com/unity/tutorials/movement/orbit-camera/
+pub fn move_camera(
+    time: Res<Time>,
+    mut camera_query: Query<(
+        &OrbitCamera,
+        Option<&mut OrbitCameraSmoothing>,
+        Option<&mut OrbitCameraCollision>,
+        &mut Transform,
+    )>,
+    mut obstacle_query: Query<(&OrbitCameraObstacle, &Transform)>,
+) {
+    for (orbit_camera, smoothing, collision, mut camera_transform) in &mut camera_query.iter() {
+        let (focus, mut position, up) = match smoothing {
+            Some(mut smoothing) => smoothing.update(orbit_camera, time.delta_seconds),
+            None => (
+                orbit_camera.focus,
+                orbit_camera.position(),
+                orbit_camera.look_up(),
+            ),
+        };
+        // checked along the smoothed view, so the camera never eases through an obstacle
+        if let Some(mut collision) = collision {
+            collision.update_obstruction(
+                focus,
+                position,
+                obstacle_query
+                    .iter()
+                    .iter()
+                    .map(|(obstacle, transform)| (*obstacle, transform.translation)),
+            );
+            position = collision.constrain(focus, position);
+        }
+        camera_transform.translation = position;
+        camera_transform.look_at(focus, up);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::assert_close;
+    use std::f32::consts::FRAC_PI_2;
+
+    #[test]
+    fn fit_distance_uses_the_vertical_fov_when_wide() {
+        // a 90 degree fov sees a unit sphere from sqrt(2) away
+        let square = OrbitCamera::fit_distance(1.0, FRAC_PI_2, 1.0);
+        assert_close(square, 2.0_f32.sqrt());
+        assert_close(OrbitCamera::fit_distance(1.0, FRAC_PI_2, 2.0), square);
+    }
+
+    #[test]
+    fn fit_distance_uses_the_horizontal_fov_when_tall() {
+        let aspect_ratio = 0.5;
+        let distance = OrbitCamera::fit_distance(1.0, FRAC_PI_2, aspect_ratio);
+        assert!(distance > OrbitCamera::fit_distance(1.0, FRAC_PI_2, 1.0));
+        // the sphere just touches the sides of the view
+        let half_fov_x = ((FRAC_PI_2 / 2.0).tan() * aspect_ratio).atan();
+        assert_close(distance * half_fov_x.sin(), 1.0);
+    }
+
+    #[test]
+    fn linear_zoom_moves_by_the_speed() {
+        let curve = ZoomCurve::Linear { speed: 2.0 };
+        assert_close(curve.apply(10.0, 1.0), 8.0);
+        assert_close(curve.apply(10.0, -0.5), 11.0);
+    }
+
+    #[test]
+    fn exponential_zoom_is_symmetric() {
+        let curve = ZoomCurve::Exponential { rate: 0.5 };
+        assert_close(curve.apply(8.0, 1.0), 4.0);
+        assert_close(curve.apply(8.0, -1.0), 16.0);
+        assert_close(curve.apply(curve.apply(8.0, 0.3), -0.3), 8.0);
+    }
+
+    #[test]
+    fn exponential_rate_is_kept_in_range() {
+        for rate in [0.0, 1.0, 1.5, -0.5].iter() {
+            let curve = ZoomCurve::exponential(*rate);
+            match curve {
+                ZoomCurve::Exponential { rate } => assert!(rate > 0.0 && rate < 1.0),
+                _ => unreachable!(),
+            }
+            for notches in [0.25, -0.25, 3.0, -3.0].iter() {
+                let distance = curve.apply(10.0, *notches);
+                assert!(distance.is_finite() && distance > 0.0, "{}", distance);
+            }
+        }
+        // constructed directly, out of range rates still zoom sensibly
+        let distance = ZoomCurve::Exponential { rate: 1.5 }.apply(10.0, 0.5);
+        assert!(distance.is_finite() && distance > 0.0, "{}", distance);
+    }
+
+    #[test]
+    fn custom_zoom_is_called() {
+        fn halve(distance: f32, _notches: f32) -> f32 {
+            distance / 2.0
+        }
+        assert_close(ZoomCurve::Custom(halve).apply(8.0, 1.0), 4.0);
+        let mut orbit_camera = OrbitCamera::new(None, 10.0, 0.0, 0.0);
+        orbit_camera.zoom_curve = ZoomCurve::Custom(halve);
+        orbit_camera.zoom(0.0);
+        assert_eq!(orbit_camera.distance(), 10.0);
+    }
+
+    #[test]
+    fn orthographic_zoom_stays_within_the_distance_limits() {
+        let fov = FRAC_PI_2;
+        let mut orbit_camera = OrbitCamera::new(None, 10.0, 0.0, 0.0);
+        orbit_camera.set_orthographic(fov);
+        let (min, max) = orbit_camera.scale_limits();
+        assert_close(min, OrbitCamera::DEFAULT_MIN_DISTANCE);
+        assert_close(max, OrbitCamera::DEFAULT_MAX_DISTANCE);
+        orbit_camera.zoom(1000.0);
+        assert_close(orbit_camera.view_half_height(fov), min);
+        orbit_camera.zoom_by(1000.0);
+        assert_close(orbit_camera.view_half_height(fov), max);
+        // tightening the limits clamps the current scale too
+        orbit_camera.set_distance_limits(5.0, 20.0).unwrap();
+        assert_close(orbit_camera.view_half_height(fov), 20.0);
+    }
+
+    #[test]
+    fn built_orthographic_scale_stays_within_the_distance_limits() {
+        let orbit_camera = OrbitCamera::builder()
+            .projection(OrbitProjection::Orthographic { scale: 1e6 })
+            .build()
+            .unwrap();
+        let (_, max) = orbit_camera.scale_limits();
+        assert_eq!(
+            orbit_camera.projection(),
+            OrbitProjection::Orthographic { scale: max }
+        );
+    }
+
+    #[test]
+    fn panning_a_group_camera_offsets_it() {
+        let mut orbit_camera = OrbitCamera::new(None, 10.0, 0.0, 0.0);
+        orbit_camera.pan_mode = PanMode::Detach;
+        orbit_camera.following_group = true;
+        orbit_camera.pan(Vec3::new(1.0, 0.0, 0.0));
+        assert_eq!(orbit_camera.pan_offset(), Vec3::new(1.0, 0.0, 0.0));
+        assert_eq!(orbit_camera.focus, Vec3::new(1.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn panning_without_a_target_only_moves_the_focus() {
+        let mut orbit_camera = OrbitCamera::new(None, 10.0, 0.0, 0.0);
+        orbit_camera.pan(Vec3::new(1.0, 0.0, 0.0));
+        assert_eq!(orbit_camera.pan_offset(), Vec3::zero());
+        assert_eq!(orbit_camera.focus, Vec3::new(1.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn relocking_a_released_target_adopts_its_preferences_again() {
+        let target = Entity::from_id(1);
+        let preferences = OrbitCameraTarget::default().with_distance(10.0);
+        let mut orbit_camera = OrbitCamera::new(Some(target), 20.0, 0.0, 0.0);
+        orbit_camera.acquire(target, &preferences, 1.0, 1.0);
+        assert_eq!(orbit_camera.distance(), 10.0);
+        // already acquired, so zooming out sticks
+        orbit_camera.set_distance(30.0);
+        orbit_camera.acquire(target, &preferences, 1.0, 1.0);
+        assert_eq!(orbit_camera.distance(), 30.0);
+        orbit_camera.release_target().target = Some(target);
+        orbit_camera.acquire(target, &preferences, 1.0, 1.0);
+        assert_eq!(orbit_camera.distance(), 10.0);
+    }
+
+    #[test]
+    fn detaching_drops_the_pan_offset() {
+        let mut orbit_camera = OrbitCamera::new(Some(Entity::from_id(1)), 10.0, 0.0, 0.0);
+        orbit_camera.pan(Vec3::new(1.0, 0.0, 0.0));
+        assert_eq!(orbit_camera.pan_offset(), Vec3::new(1.0, 0.0, 0.0));
+        orbit_camera.pan_mode = PanMode::Detach;
+        orbit_camera.pan(Vec3::new(1.0, 0.0, 0.0));
+        assert_eq!(orbit_camera.target, None);
+        assert_eq!(orbit_camera.pan_offset(), Vec3::zero());
+        assert_eq!(orbit_camera.focus, Vec3::new(2.0, 0.0, 0.0));
+    }
+}
@@ -0,0 +1,41 @@
+use crate::OrbitCamera;
+use bevy::prelude::*;
+use bevy::render::camera::{Camera, CameraProjection, PerspectiveProjection};
+
+/// The projection an `OrbitCamera` renders with
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OrbitProjection {
+    /// Uses the camera's `PerspectiveProjection`, and zooming changes the distance
+    Perspective,
+    /// Zooming changes the `scale`, which is half the height of the view in world units
+    Orthographic { scale: f32 },
+}
+
+impl Default for OrbitProjection {
+    fn default() -> Self {
+        OrbitProjection::Perspective
+    }
+}
+
+// core
+// bevy only updates the projection matrix when the window changes, so this takes over for
+// orbit cameras, and runs after bevy's camera systems so that it isn't overwritten
+pub fn update_projection(
+    mut camera_query: Query<(&OrbitCamera, &mut Camera, Option<&PerspectiveProjection>)>,
+) {
+    for (orbit_camera, mut camera, perspective_projection) in &mut camera_query.iter() {
+        let perspective_projection = match perspective_projection {
+            Some(perspective_projection) => perspective_projection,
+            None => continue,
+        };
+        camera.projection_matrix = match orbit_camera.projection() {
+            OrbitProjection::Perspective => perspective_projection.get_projection_matrix(),
+            OrbitProjection::Orthographic { scale } => {
+                let half_width = scale * perspective_projection.aspect_ratio;
+                let far = perspective_projection.far;
+                // anything behind the camera is still in view, rather than clipped
+                Mat4::orthographic_rh(-half_width, half_width, -scale, scale, -far, far)
+            }
+        };
+    }
+}
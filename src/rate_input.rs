@@ -0,0 +1,320 @@
+use crate::OrbitCamera;
+use bevy::input::gamepad::{
+    Gamepad, GamepadAxis, GamepadAxisType, GamepadButton, GamepadButtonType, GamepadEvent,
+    GamepadEventType,
+};
+use bevy::input::Axis;
+use bevy::prelude::*;
+use std::f32::consts::PI;
+
+/// Shapes the raw value of an analog axis, from -1 to 1
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AxisResponse {
+    /// Values closer to 0 than this are ignored, and the rest rescaled to start from 0
+    pub dead_zone: f32,
+    /// Above 1, small movements are finer and large movements coarser
+    pub exponent: f32,
+}
+
+impl Default for AxisResponse {
+    fn default() -> Self {
+        Self {
+            dead_zone: 0.15,
+            exponent: 2.0,
+        }
+    }
+}
+
+impl AxisResponse {
+    pub fn apply(&self, value: f32) -> f32 {
+        let magnitude = value.abs().min(1.0);
+        if magnitude <= self.dead_zone {
+            return 0.0;
+        }
+        let magnitude = (magnitude - self.dead_zone) / (1.0 - self.dead_zone);
+        magnitude.powf(self.exponent) * value.signum()
+    }
+}
+
+/// Keys that turn the camera the same way as pushing the right stick; `None` disables a key
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct KeyboardBindings {
+    pub left: Option<KeyCode>,
+    pub right: Option<KeyCode>,
+    pub up: Option<KeyCode>,
+    pub down: Option<KeyCode>,
+    pub zoom_in: Option<KeyCode>,
+    pub zoom_out: Option<KeyCode>,
+    pub roll_left: Option<KeyCode>,
+    pub roll_right: Option<KeyCode>,
+}
+
+impl Default for KeyboardBindings {
+    fn default() -> Self {
+        Self {
+            left: Some(KeyCode::Left),
+            right: Some(KeyCode::Right),
+            up: Some(KeyCode::Up),
+            down: Some(KeyCode::Down),
+            zoom_in: Some(KeyCode::PageUp),
+            zoom_out: Some(KeyCode::PageDown),
+            roll_left: None,
+            roll_right: None,
+        }
+    }
+}
+
+/// An analog axis, or a button such as a trigger that reports how far it's pressed
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GamepadInput {
+    Axis(GamepadAxisType),
+    Button(GamepadButtonType),
+}
+
+impl GamepadInput {
+    fn value(
+        &self,
+        gamepad: Gamepad,
+        axes: &Axis<GamepadAxis>,
+        button_axes: &Axis<GamepadButton>,
+    ) -> f32 {
+        match *self {
+            GamepadInput::Axis(axis_type) => axes.get(&GamepadAxis(gamepad, axis_type)),
+            GamepadInput::Button(button_type) => {
+                button_axes.get(&GamepadButton(gamepad, button_type))
+            }
+        }
+        .unwrap_or(0.0)
+    }
+}
+
+/// The gamepad inputs that turn and zoom the camera; `None` disables an input
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GamepadBindings {
+    pub yaw: Option<GamepadInput>,
+    pub pitch: Option<GamepadInput>,
+    pub zoom_in: Option<GamepadInput>,
+    pub zoom_out: Option<GamepadInput>,
+    pub roll: Option<GamepadInput>,
+    pub response: AxisResponse,
+}
+
+impl Default for GamepadBindings {
+    fn default() -> Self {
+        Self {
+            yaw: Some(GamepadInput::Axis(GamepadAxisType::RightStickX)),
+            pitch: Some(GamepadInput::Axis(GamepadAxisType::RightStickY)),
+            zoom_in: Some(GamepadInput::Button(GamepadButtonType::RightTrigger2)),
+            zoom_out: Some(GamepadInput::Button(GamepadButtonType::LeftTrigger2)),
+            roll: None,
+            response: AxisResponse::default(),
+        }
+    }
+}
+
+/// Turns and zooms the camera at a rate while keys are held or the gamepad is pushed,
+/// for when there's no mouse
+#[derive(Debug, Clone, PartialEq)]
+pub struct OrbitCameraRateInput {
+    pub keyboard: KeyboardBindings,
+    pub gamepad: GamepadBindings,
+    /// Radians per second when fully pushed
+    pub yaw_speed: f32,
+    /// Radians per second when fully pushed
+    pub pitch_speed: f32,
+    /// Notches of zoom, as with the mouse wheel, per second when fully pushed
+    pub zoom_speed: f32,
+    /// Radians per second when fully pushed
+    pub roll_speed: f32,
+    pub invert_yaw: bool,
+    pub invert_pitch: bool,
+}
+
+impl Default for OrbitCameraRateInput {
+    fn default() -> Self {
+        Self {
+            keyboard: KeyboardBindings::default(),
+            gamepad: GamepadBindings::default(),
+            yaw_speed: PI,
+            pitch_speed: PI / 2.0,
+            zoom_speed: 10.0,
+            roll_speed: PI / 2.0,
+            invert_yaw: false,
+            invert_pitch: false,
+        }
+    }
+}
+
+impl OrbitCameraRateInput {
+    // pushing right or up turns the view right or up, which moves the camera the other way,
+    // and rolling right tilts the top of the view to the right
+    fn apply(&self, orbit_camera: &mut OrbitCamera, rates: Rates, dt: f32) {
+        let yaw = if self.invert_yaw {
+            rates.yaw
+        } else {
+            -rates.yaw
+        };
+        let pitch = if self.invert_pitch {
+            rates.pitch
+        } else {
+            -rates.pitch
+        };
+        orbit_camera
+            .add_yaw(yaw * self.yaw_speed * dt)
+            .add_pitch(pitch * self.pitch_speed * dt)
+            .zoom(rates.zoom * self.zoom_speed * dt);
+        // only while rolling, so as not to hold off `AutoLevel`
+        if rates.roll != 0.0 {
+            orbit_camera.add_roll(-rates.roll * self.roll_speed * dt);
+        }
+    }
+}
+
+/// How far each way the camera is being pushed, from -1 to 1
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Rates {
+    yaw: f32,
+    pitch: f32,
+    zoom: f32,
+    roll: f32,
+}
+
+impl Rates {
+    fn is_zero(&self) -> bool {
+        self.yaw == 0.0 && self.pitch == 0.0 && self.zoom == 0.0 && self.roll == 0.0
+    }
+}
+
+// default implementation
+pub fn keyboard_camera(
+    time: Res<Time>,
+    input: Res<OrbitCameraRateInput>,
+    keyboard_input: Res<Input<KeyCode>>,
+    mut camera_query: Query<&mut OrbitCamera>,
+) {
+    let keys = &input.keyboard;
+    let axis = |negative: Option<KeyCode>, positive: Option<KeyCode>| {
+        let pressed = |key: Option<KeyCode>| key.map_or(false, |key| keyboard_input.pressed(key));
+        match (pressed(negative), pressed(positive)) {
+            (true, false) => -1.0,
+            (false, true) => 1.0,
+            _ => 0.0,
+        }
+    };
+    let rates = Rates {
+        yaw: axis(keys.left, keys.right),
+        pitch: axis(keys.down, keys.up),
+        zoom: axis(keys.zoom_out, keys.zoom_in),
+        roll: axis(keys.roll_left, keys.roll_right),
+    };
+    if rates.is_zero() {
+        return;
+    }
+    for mut orbit_camera in &mut camera_query.iter() {
+        input.apply(&mut orbit_camera, rates, time.delta_seconds);
+    }
+}
+
+/// The gamepads currently connected
+#[derive(Default)]
+pub struct GamepadState {
+    gamepad_event_reader: EventReader<GamepadEvent>,
+    gamepads: Vec<Gamepad>,
+}
+
+// default implementation
+pub fn gamepad_camera(
+    mut gamepad_state: Local<GamepadState>,
+    gamepad_events: Res<Events<GamepadEvent>>,
+    time: Res<Time>,
+    input: Res<OrbitCameraRateInput>,
+    axes: Res<Axis<GamepadAxis>>,
+    button_axes: Res<Axis<GamepadButton>>,
+    mut camera_query: Query<&mut OrbitCamera>,
+) {
+    let gamepad_state = &mut *gamepad_state;
+    for GamepadEvent(gamepad, event_type) in
+        gamepad_state.gamepad_event_reader.iter(&gamepad_events)
+    {
+        match event_type {
+            GamepadEventType::Connected => {
+                if !gamepad_state.gamepads.contains(gamepad) {
+                    gamepad_state.gamepads.push(*gamepad);
+                }
+            }
+            GamepadEventType::Disconnected => {
+                gamepad_state
+                    .gamepads
+                    .retain(|connected| connected != gamepad);
+            }
+        }
+    }
+    let bindings = &input.gamepad;
+    let value = |binding: Option<GamepadInput>| {
+        gamepad_state
+            .gamepads
+            .iter()
+            .map(|gamepad| {
+                binding.map_or(0.0, |binding| binding.value(*gamepad, &axes, &button_axes))
+            })
+            .sum::<f32>()
+            .max(-1.0)
+            .min(1.0)
+    };
+    let rates = Rates {
+        yaw: bindings.response.apply(value(bindings.yaw)),
+        pitch: bindings.response.apply(value(bindings.pitch)),
+        zoom: bindings.response.apply(value(bindings.zoom_in))
+            - bindings.response.apply(value(bindings.zoom_out)),
+        roll: bindings.response.apply(value(bindings.roll)),
+    };
+    if rates.is_zero() {
+        return;
+    }
+    for mut orbit_camera in &mut camera_query.iter() {
+        input.apply(&mut orbit_camera, rates, time.delta_seconds);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::assert_close;
+
+    #[test]
+    fn dead_zone_is_ignored() {
+        let response = AxisResponse::default();
+        assert_eq!(response.apply(0.0), 0.0);
+        assert_eq!(response.apply(0.1), 0.0);
+        assert_eq!(response.apply(-0.15), 0.0);
+    }
+
+    #[test]
+    fn full_deflection_stays_full() {
+        let response = AxisResponse::default();
+        assert_close(response.apply(1.0), 1.0);
+        assert_close(response.apply(-1.0), -1.0);
+        // some gamepads report slightly more than 1
+        assert_close(response.apply(1.2), 1.0);
+    }
+
+    #[test]
+    fn rescaled_past_the_dead_zone_then_curved() {
+        let response = AxisResponse {
+            dead_zone: 0.2,
+            exponent: 2.0,
+        };
+        // halfway between the dead zone and 1, squared
+        assert_close(response.apply(0.6), 0.25);
+        assert_close(response.apply(-0.6), -0.25);
+    }
+
+    #[test]
+    fn linear_without_dead_zone() {
+        let response = AxisResponse {
+            dead_zone: 0.0,
+            exponent: 1.0,
+        };
+        assert_close(response.apply(0.3), 0.3);
+    }
+}
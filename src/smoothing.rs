@@ -0,0 +1,178 @@
+use crate::{OrbitCamera, OrbitMode};
+use bevy::prelude::*;
+use std::f32::consts::PI;
+use std::ops::{Add, Mul, Sub};
+
+/// A damped spring pulling a value towards its target
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Spring {
+    /// How strongly the value is pulled towards the target, higher is snappier
+    pub stiffness: f32,
+    /// How strongly the velocity is resisted, higher settles with less overshoot
+    pub damping: f32,
+}
+
+impl Spring {
+    pub fn new(stiffness: f32, damping: f32) -> Self {
+        Self { stiffness, damping }
+    }
+    /// A spring that settles as fast as possible without overshooting
+    pub fn critically_damped(stiffness: f32) -> Self {
+        Self {
+            stiffness,
+            damping: 2.0 * stiffness.sqrt(),
+        }
+    }
+    // semi-implicit euler, which is stable enough for the small steps `OrbitCameraSmoothing` takes
+    fn step<T>(&self, current: T, velocity: T, target: T, dt: f32) -> (T, T)
+    where
+        T: Copy + Add<Output = T> + Sub<Output = T> + Mul<f32, Output = T>,
+    {
+        let acceleration = (target - current) * self.stiffness - velocity * self.damping;
+        let velocity = velocity + acceleration * dt;
+        (current + velocity * dt, velocity)
+    }
+}
+
+/// Add this to an `OrbitCamera` entity to ease the camera towards the `OrbitCamera`
+/// each frame, rather than snapping to it; a trackball eases the way it faces and its up
+/// with the yaw spring
+#[derive(Debug, Clone)]
+pub struct OrbitCameraSmoothing {
+    pub focus: Spring,
+    pub distance: Spring,
+    pub pitch: Spring,
+    pub yaw: Spring,
+    state: Option<SmoothedState>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct SmoothedState {
+    focus: Vec3,
+    focus_velocity: Vec3,
+    distance: f32,
+    distance_velocity: f32,
+    pitch: f32,
+    pitch_velocity: f32,
+    yaw: f32,
+    yaw_velocity: f32,
+    backwards: Vec3,
+    backwards_velocity: Vec3,
+    up: Vec3,
+    up_velocity: Vec3,
+}
+
+impl Default for OrbitCameraSmoothing {
+    fn default() -> Self {
+        Self::uniform(Spring::critically_damped(50.0))
+    }
+}
+
+impl OrbitCameraSmoothing {
+    /// The largest step in seconds the springs are advanced by at once, so that
+    /// the result doesn't depend on the frame rate
+    const MAX_STEP: f32 = 1.0 / 240.0;
+    pub fn new(focus: Spring, distance: Spring, pitch: Spring, yaw: Spring) -> Self {
+        Self {
+            focus,
+            distance,
+            pitch,
+            yaw,
+            state: None,
+        }
+    }
+    /// Uses the same spring for focus, distance, pitch and yaw
+    pub fn uniform(spring: Spring) -> Self {
+        Self::new(spring, spring, spring, spring)
+    }
+    /// Snaps to the `OrbitCamera` on the next update, e.g. after a cut
+    pub fn reset(&mut self) -> &mut Self {
+        self.state = None;
+        self
+    }
+    /// Advances the springs by `dt` seconds towards `orbit_camera`, and returns the
+    /// smoothed focus, position and which way is up
+    pub fn update(&mut self, orbit_camera: &OrbitCamera, dt: f32) -> (Vec3, Vec3, Vec3) {
+        let backwards_target = (orbit_camera.position() - orbit_camera.focus).normalize();
+        let up_target = orbit_camera.look_up();
+        let mut state = self.state.unwrap_or(SmoothedState {
+            focus: orbit_camera.focus,
+            focus_velocity: Vec3::zero(),
+            distance: orbit_camera.distance(),
+            distance_velocity: 0.0,
+            pitch: orbit_camera.world_pitch(),
+            pitch_velocity: 0.0,
+            yaw: orbit_camera.world_yaw(),
+            yaw_velocity: 0.0,
+            backwards: backwards_target,
+            backwards_velocity: Vec3::zero(),
+            up: up_target,
+            up_velocity: Vec3::zero(),
+        });
+        // take the shortest way around to the target yaw
+        let yaw_target =
+            state.yaw + OrbitCamera::wrap(orbit_camera.world_yaw() - state.yaw, -PI, PI);
+        let mut remaining = dt;
+        while remaining > 0.0 {
+            let step = remaining.min(Self::MAX_STEP);
+            let (focus, focus_velocity) =
+                self.focus
+                    .step(state.focus, state.focus_velocity, orbit_camera.focus, step);
+            let (distance, distance_velocity) = self.distance.step(
+                state.distance,
+                state.distance_velocity,
+                orbit_camera.distance(),
+                step,
+            );
+            let (pitch, pitch_velocity) = self.pitch.step(
+                state.pitch,
+                state.pitch_velocity,
+                orbit_camera.world_pitch(),
+                step,
+            );
+            let (yaw, yaw_velocity) =
+                self.yaw
+                    .step(state.yaw, state.yaw_velocity, yaw_target, step);
+            let (backwards, backwards_velocity) = self.yaw.step(
+                state.backwards,
+                state.backwards_velocity,
+                backwards_target,
+                step,
+            );
+            let (up, up_velocity) = self.yaw.step(state.up, state.up_velocity, up_target, step);
+            state = SmoothedState {
+                focus,
+                focus_velocity,
+                distance,
+                distance_velocity,
+                pitch,
+                pitch_velocity,
+                yaw,
+                yaw_velocity,
+                backwards,
+                backwards_velocity,
+                up,
+                up_velocity,
+            };
+            remaining -= step;
+        }
+        state.yaw = OrbitCamera::wrap(state.yaw, -PI, PI);
+        // overshooting the pitch past the poles would flip the camera over
+        state.pitch = state
+            .pitch
+            .max(OrbitCamera::MIN_PITCH)
+            .min(OrbitCamera::MAX_PITCH);
+        state.distance = state.distance.max(f32::EPSILON);
+        self.state = Some(state);
+        let backwards = match orbit_camera.mode() {
+            OrbitMode::Turntable => {
+                orbit_camera.orbit_frame()
+                    * OrbitCamera::calculate_relative_position(state.pitch, state.yaw, 1.0)
+            }
+            // springing the direction rather than the angles keeps it steady over the poles
+            OrbitMode::Trackball => state.backwards.normalize(),
+        };
+        let position = state.focus + backwards * state.distance;
+        (state.focus, position, state.up.normalize())
+    }
+}
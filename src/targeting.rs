@@ -0,0 +1,381 @@
+use crate::{Easing, OrbitCamera, OrbitCameraTarget};
+use bevy::prelude::*;
+
+/// What an `OrbitCamera` does when its target is despawned or stops being an
+/// `OrbitCameraTarget`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TargetLossPolicy {
+    /// Stay where it is, and carry on following the target if it becomes one again
+    Hold,
+    /// Let go of the target and go back to the home view
+    ReturnHome,
+    /// Switch to whichever other `OrbitCameraTarget` is nearest the focus, or let go of the
+    /// target if there are none
+    RetargetNearest,
+    /// Let go of the target, staying where it is
+    Clear,
+}
+
+impl Default for TargetLossPolicy {
+    fn default() -> Self {
+        TargetLossPolicy::Hold
+    }
+}
+
+/// Sent once each time an `OrbitCamera` loses its target, after applying its
+/// `TargetLossPolicy`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OrbitCameraTargetLost {
+    pub camera: Entity,
+    /// The target that was lost
+    pub target: Entity,
+    /// What the camera targets now, if anything
+    pub new_target: Option<Entity>,
+}
+
+impl TargetLossPolicy {
+    /// Applies the policy to a camera that has just lost `lost`, returning its new target
+    pub(crate) fn apply(
+        &self,
+        orbit_camera: &mut OrbitCamera,
+        lost: Entity,
+        target_query: &mut Query<(Entity, &OrbitCameraTarget, &Transform)>,
+    ) -> Option<Entity> {
+        match *self {
+            TargetLossPolicy::Hold => {}
+            TargetLossPolicy::ReturnHome => {
+                orbit_camera.release_target().reset();
+            }
+            TargetLossPolicy::RetargetNearest => {
+                let focus = orbit_camera.focus;
+                orbit_camera.target = target_query
+                    .iter()
+                    .iter()
+                    .filter(|(entity, _, _)| *entity != lost)
+                    .map(|(entity, _, transform)| {
+                        (entity, (transform.translation - focus).length_squared())
+                    })
+                    .fold(
+                        None,
+                        |nearest: Option<(Entity, f32)>, (entity, distance)| match nearest {
+                            Some((_, nearest_distance)) if nearest_distance <= distance => nearest,
+                            _ => Some((entity, distance)),
+                        },
+                    )
+                    .map(|(entity, _)| entity);
+                // the offset was from the old target
+                orbit_camera.pan_offset = Vec3::zero();
+            }
+            TargetLossPolicy::Clear => {
+                orbit_camera.release_target();
+            }
+        }
+        orbit_camera.target
+    }
+}
+
+/// The order `OrbitCameraSwitchTarget` cycles through every `OrbitCameraTarget` in
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TargetOrder {
+    /// Nearest the camera first
+    Distance,
+    /// Left to right across the view
+    Screen,
+    /// Highest `OrbitCameraTarget::priority` first, then nearest the camera
+    Priority,
+}
+
+impl Default for TargetOrder {
+    fn default() -> Self {
+        TargetOrder::Distance
+    }
+}
+
+impl TargetOrder {
+    /// Sorts the targets' translations for a camera
+    fn sort(&self, orbit_camera: &OrbitCamera, targets: &mut Vec<(Entity, i32, Vec3)>) {
+        let position = orbit_camera.position();
+        let forward = (orbit_camera.focus - position).normalize();
+        let right = orbit_camera.right();
+        let distance = |translation: Vec3| (translation - position).length();
+        let screen_angle = |translation: Vec3| {
+            let offset = translation - position;
+            offset.dot(right).atan2(offset.dot(forward))
+        };
+        targets.sort_by(|(_, priority_a, a), (_, priority_b, b)| {
+            let (a, b) = match *self {
+                TargetOrder::Distance => (distance(*a), distance(*b)),
+                TargetOrder::Screen => (screen_angle(*a), screen_angle(*b)),
+                TargetOrder::Priority => {
+                    if priority_a != priority_b {
+                        return priority_b.cmp(priority_a);
+                    }
+                    (distance(*a), distance(*b))
+                }
+            };
+            a.partial_cmp(&b).unwrap_or(std::cmp::Ordering::Equal)
+        });
+    }
+}
+
+/// The order a camera is cycling through its targets in, sorted from wherever the camera is
+/// when it starts cycling without a target, so that it doesn't change as the camera moves
+/// from target to target
+#[derive(Debug, Clone, Default)]
+pub(crate) struct TargetCycle {
+    order: Option<TargetOrder>,
+    targets: Vec<Entity>,
+}
+
+impl TargetCycle {
+    /// The target after (or before) the camera's current one, or the first (or last) if it
+    /// has none; the targets are sorted again if the camera has no target, or one outside
+    /// the cycle, or if they or the order change
+    fn step(
+        &mut self,
+        orbit_camera: &OrbitCamera,
+        order: TargetOrder,
+        forwards: bool,
+        mut targets: Vec<(Entity, i32, Vec3)>,
+    ) -> Option<Entity> {
+        if targets.is_empty() {
+            return None;
+        }
+        let unchanged = self.order == Some(order)
+            && orbit_camera
+                .target
+                .map_or(false, |current| self.targets.contains(&current))
+            && self.targets.len() == targets.len()
+            && targets
+                .iter()
+                .all(|(target, _, _)| self.targets.contains(target));
+        if !unchanged {
+            order.sort(orbit_camera, &mut targets);
+            self.order = Some(order);
+            self.targets = targets.iter().map(|(target, _, _)| *target).collect();
+        }
+        let count = self.targets.len();
+        let current = orbit_camera
+            .target
+            .and_then(|current| self.targets.iter().position(|target| *target == current));
+        let index = match (current, forwards) {
+            (Some(index), true) => (index + 1) % count,
+            (Some(index), false) => (index + count - 1) % count,
+            (None, true) => 0,
+            (None, false) => count - 1,
+        };
+        Some(self.targets[index])
+    }
+}
+
+/// Which target an `OrbitCameraSwitchTarget` switches to
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SwitchTo {
+    /// The target after the current one, or the first if the camera has none
+    Next(TargetOrder),
+    /// The target before the current one, or the last if the camera has none
+    Previous(TargetOrder),
+    /// A particular entity, e.g. to lock on to it
+    Target(Entity),
+    /// No target, leaving the focus where it is
+    None,
+}
+
+/// Asks a camera to switch to another target
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OrbitCameraSwitchTarget {
+    pub camera: Entity,
+    pub to: SwitchTo,
+    /// How long to ease the focus onto the new target for and how, or `None` to cut
+    /// straight to it
+    pub animation: Option<(f32, Easing)>,
+}
+
+impl OrbitCameraSwitchTarget {
+    pub const DEFAULT_DURATION: f32 = 0.3;
+    /// Switches, easing onto the new target over `DEFAULT_DURATION`
+    pub fn new(camera: Entity, to: SwitchTo) -> Self {
+        Self {
+            camera,
+            to,
+            animation: Some((Self::DEFAULT_DURATION, Easing::default())),
+        }
+    }
+    pub fn next(camera: Entity, order: TargetOrder) -> Self {
+        Self::new(camera, SwitchTo::Next(order))
+    }
+    pub fn previous(camera: Entity, order: TargetOrder) -> Self {
+        Self::new(camera, SwitchTo::Previous(order))
+    }
+    pub fn lock_on(camera: Entity, target: Entity) -> Self {
+        Self::new(camera, SwitchTo::Target(target))
+    }
+    pub fn release(camera: Entity) -> Self {
+        Self::new(camera, SwitchTo::None)
+    }
+    pub fn with_animation(mut self, duration: f32, easing: Easing) -> Self {
+        self.animation = Some((duration, easing));
+        self
+    }
+    pub fn without_animation(mut self) -> Self {
+        self.animation = None;
+        self
+    }
+}
+
+/// Sent when a camera switches targets with an `OrbitCameraSwitchTarget`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OrbitCameraTargetSwitched {
+    pub camera: Entity,
+    pub previous: Option<Entity>,
+    pub target: Option<Entity>,
+}
+
+// core
+pub fn switch_target(
+    mut switch_event_reader: Local<EventReader<OrbitCameraSwitchTarget>>,
+    switch_events: Res<Events<OrbitCameraSwitchTarget>>,
+    mut switched_events: ResMut<Events<OrbitCameraTargetSwitched>>,
+    mut camera_query: Query<(Entity, &mut OrbitCamera)>,
+    mut target_query: Query<(Entity, &OrbitCameraTarget, &Transform)>,
+) {
+    for event in switch_event_reader.iter(&switch_events) {
+        for (entity, mut orbit_camera) in &mut camera_query.iter() {
+            if entity != event.camera {
+                continue;
+            }
+            // taken out while it steps, as it needs to see the rest of the camera
+            let mut cycle = std::mem::take(&mut orbit_camera.target_cycle);
+            let target = match event.to {
+                SwitchTo::Next(order) => cycle.step(
+                    &orbit_camera,
+                    order,
+                    true,
+                    cycle_targets(entity, &mut target_query),
+                ),
+                SwitchTo::Previous(order) => cycle.step(
+                    &orbit_camera,
+                    order,
+                    false,
+                    cycle_targets(entity, &mut target_query),
+                ),
+                SwitchTo::Target(target) => Some(target),
+                SwitchTo::None => None,
+            };
+            let previous = orbit_camera.target;
+            if target == previous {
+                orbit_camera.target_cycle = cycle;
+                continue;
+            }
+            // let go of the old target and its offset, so the new one is acquired afresh
+            orbit_camera.release_target().target = target;
+            // but carry on cycling in the same order, unless letting go altogether
+            if target.is_some() {
+                orbit_camera.target_cycle = cycle;
+            }
+            orbit_camera.target_lost = false;
+            if let (Some(_), Some((duration, easing))) = (target, event.animation) {
+                // the animation's focus follows the new target, easing the camera onto it
+                let view = orbit_camera.view();
+                orbit_camera.animate_to(view, duration, easing);
+            }
+            switched_events.send(OrbitCameraTargetSwitched {
+                camera: entity,
+                previous,
+                target,
+            });
+        }
+    }
+}
+
+/// Every target a camera can cycle through
+fn cycle_targets(
+    camera: Entity,
+    target_query: &mut Query<(Entity, &OrbitCameraTarget, &Transform)>,
+) -> Vec<(Entity, i32, Vec3)> {
+    target_query
+        .iter()
+        .iter()
+        .filter(|(target, _, _)| *target != camera)
+        .map(|(target, target_info, transform)| {
+            (target, target_info.priority, transform.translation)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cycling_visits_every_target() {
+        // a and b are close together, so re-sorting from each one would flip between them
+        let targets = vec![
+            (Entity::from_id(1), 0, Vec3::zero()),
+            (Entity::from_id(2), 0, Vec3::new(1.0, 0.0, 0.0)),
+            (Entity::from_id(3), 0, Vec3::new(0.0, 0.0, -30.0)),
+        ];
+        let mut orbit_camera = OrbitCamera::new(None, 10.0, 0.0, 0.0);
+        let mut cycle = TargetCycle::default();
+        let mut visited = Vec::new();
+        for _ in 0..targets.len() {
+            let target = cycle.step(&orbit_camera, TargetOrder::Distance, true, targets.clone());
+            orbit_camera.target = target;
+            let (entity, _, translation) = targets
+                .iter()
+                .find(|(entity, _, _)| Some(*entity) == target)
+                .unwrap();
+            orbit_camera.focus = *translation;
+            visited.push(*entity);
+        }
+        assert_eq!(
+            visited,
+            vec![Entity::from_id(1), Entity::from_id(2), Entity::from_id(3)]
+        );
+        // and then wraps around
+        assert_eq!(
+            cycle.step(&orbit_camera, TargetOrder::Distance, true, targets),
+            Some(Entity::from_id(1))
+        );
+    }
+
+    #[test]
+    fn cycling_sorts_again_when_the_targets_change() {
+        let mut targets = vec![
+            (Entity::from_id(1), 0, Vec3::new(20.0, 0.0, 0.0)),
+            (Entity::from_id(2), 0, Vec3::new(40.0, 0.0, 0.0)),
+        ];
+        let mut orbit_camera = OrbitCamera::new(None, 10.0, 0.0, 0.0);
+        let mut cycle = TargetCycle::default();
+        orbit_camera.target =
+            cycle.step(&orbit_camera, TargetOrder::Distance, true, targets.clone());
+        assert_eq!(orbit_camera.target, Some(Entity::from_id(1)));
+        // a new target between the two comes next
+        targets.push((Entity::from_id(3), 0, Vec3::new(30.0, 0.0, 0.0)));
+        assert_eq!(
+            cycle.step(&orbit_camera, TargetOrder::Distance, true, targets),
+            Some(Entity::from_id(3))
+        );
+    }
+
+    #[test]
+    fn cycling_without_a_target_starts_from_the_nearest_now() {
+        let targets = vec![
+            (Entity::from_id(1), 0, Vec3::new(-50.0, 0.0, 0.0)),
+            (Entity::from_id(2), 0, Vec3::new(50.0, 0.0, 0.0)),
+        ];
+        let mut orbit_camera = OrbitCamera::new(None, 10.0, 0.0, 0.0);
+        let mut cycle = TargetCycle::default();
+        orbit_camera.focus = Vec3::new(-50.0, 0.0, 0.0);
+        assert_eq!(
+            cycle.step(&orbit_camera, TargetOrder::Distance, true, targets.clone()),
+            Some(Entity::from_id(1))
+        );
+        // the player has moved across the level since
+        orbit_camera.focus = Vec3::new(50.0, 0.0, 0.0);
+        assert_eq!(
+            cycle.step(&orbit_camera, TargetOrder::Distance, true, targets),
+            Some(Entity::from_id(2))
+        );
+    }
+}
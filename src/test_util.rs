@@ -0,0 +1,29 @@
+use bevy::prelude::*;
+use std::fmt::Debug;
+
+/// Something with a distance between its values, for comparing them in tests
+pub(crate) trait Close: Copy + Debug {
+    fn distance(self, other: Self) -> f32;
+}
+
+impl Close for f32 {
+    fn distance(self, other: Self) -> f32 {
+        (self - other).abs()
+    }
+}
+
+impl Close for Vec3 {
+    fn distance(self, other: Self) -> f32 {
+        (self - other).length()
+    }
+}
+
+/// Asserts that `actual` is within rounding error of `expected`
+pub(crate) fn assert_close<T: Close>(actual: T, expected: T) {
+    assert!(
+        actual.distance(expected) < 1e-4,
+        "{:?} is not {:?}",
+        actual,
+        expected
+    );
+}
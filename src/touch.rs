@@ -0,0 +1,96 @@
+use crate::input::{pan_by_pixels, window_size};
+use crate::{OrbitCamera, OrbitSensitivity};
+use bevy::input::touch::{TouchInput, TouchPhase};
+use bevy::prelude::*;
+use bevy::render::camera::PerspectiveProjection;
+use bevy::window::Windows;
+use std::collections::HashMap;
+
+/// Configures the touch gestures: dragging one finger orbits, and with two fingers,
+/// pinching zooms and dragging pans
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OrbitCameraTouchInput {
+    pub orbit: bool,
+    pub pinch_zoom: bool,
+    pub pan: bool,
+    pub orbit_sensitivity: OrbitSensitivity,
+}
+
+impl Default for OrbitCameraTouchInput {
+    fn default() -> Self {
+        Self {
+            orbit: true,
+            pinch_zoom: true,
+            pan: true,
+            orbit_sensitivity: OrbitSensitivity::default(),
+        }
+    }
+}
+
+/// Where each finger currently on the screen is
+#[derive(Default)]
+pub struct TouchState {
+    touch_event_reader: EventReader<TouchInput>,
+    touches: HashMap<u64, Vec2>,
+}
+
+// default implementation
+pub fn touch_camera(
+    mut touch_state: Local<TouchState>,
+    touch_events: Res<Events<TouchInput>>,
+    input: Res<OrbitCameraTouchInput>,
+    windows: Res<Windows>,
+    mut camera_query: Query<(&mut OrbitCamera, Option<&PerspectiveProjection>)>,
+) {
+    let touch_state = &mut *touch_state;
+    let previous = touch_state.touches.clone();
+    for event in touch_state.touch_event_reader.iter(&touch_events) {
+        match event.phase {
+            TouchPhase::Started | TouchPhase::Moved => {
+                touch_state.touches.insert(event.id, event.position);
+            }
+            TouchPhase::Ended | TouchPhase::Cancelled => {
+                touch_state.touches.remove(&event.id);
+            }
+        }
+    }
+    // only fingers that were already down count, so a finger landing doesn't jump the camera
+    let moved = touch_state
+        .touches
+        .iter()
+        .filter_map(|(id, position)| previous.get(id).map(|previous| (*previous, *position)))
+        .collect::<Vec<_>>();
+    if moved.len() != touch_state.touches.len() {
+        return;
+    }
+    // touches have no window, so they're taken to be on the primary window
+    let window_size = window_size(&windows, None);
+    match moved.as_slice() {
+        [(previous, current)] if input.orbit => {
+            for (mut orbit_camera, _) in &mut camera_query.iter() {
+                input.orbit_sensitivity.rotate(
+                    &mut orbit_camera,
+                    *current - *previous,
+                    window_size,
+                );
+            }
+        }
+        [(previous_a, current_a), (previous_b, current_b)] => {
+            let previous_span = (*previous_a - *previous_b).length();
+            let current_span = (*current_a - *current_b).length();
+            let delta = (*current_a + *current_b - *previous_a - *previous_b) / 2.0;
+            for (mut orbit_camera, perspective_projection) in &mut camera_query.iter() {
+                if input.pinch_zoom && previous_span > 0.0 && current_span > 0.0 {
+                    // spreading the fingers apart brings the camera closer
+                    orbit_camera.zoom_by(previous_span / current_span);
+                }
+                if input.pan {
+                    let fov = perspective_projection
+                        .map_or(PerspectiveProjection::default().fov, |p| p.fov);
+                    pan_by_pixels(&mut orbit_camera, delta, window_size, fov);
+                }
+            }
+        }
+        _ => {}
+    }
+}